@@ -1,22 +1,34 @@
+use super::autosave::Autosave;
+use super::error::{FileError, Operation};
 use super::{context, ctx, renderer, EventTarget, Renderer, State, Tree};
 use crate::{
     app::event_target::WheelEvent,
-    data::{entity::EntityType, project::TokenVec, Project},
+    data::{
+        codegen::{self, Language},
+        diagram::Diagram,
+        file::Format,
+        import,
+        validation::{self, Severity},
+        Project,
+    },
     elements::toolbox_item::Tool,
     geometry::{Point, Vec2},
 };
-use ogrim::xml;
 #[cfg(not(target_arch = "wasm32"))]
-use rfd::FileDialog;
-use std::{cell::RefCell, fmt, io::Write, rc::Rc};
+use rfd::{FileDialog, MessageButtons, MessageDialog, MessageDialogResult};
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
+use std::path::PathBuf;
+use std::{cell::RefCell, fmt, rc::Rc};
 use winit::{
     application::ApplicationHandler,
     event::{ElementState, WindowEvent},
-    event_loop::{ActiveEventLoop, EventLoopProxy},
+    event_loop::{ActiveEventLoop, ControlFlow, EventLoopProxy},
+    keyboard::Key,
     window::CursorIcon,
 };
-use zip::write::FileOptions;
-use zip::ZipWriter;
+
+type TreeModifier = Box<dyn FnOnce(&mut Tree, &mut context::EventContext)>;
 
 pub enum AppUserEvent {
     #[cfg(target_arch = "wasm32")]
@@ -27,11 +39,20 @@ pub enum AppUserEvent {
     RequestRedraw,
     RequestCursorUpdate,
     RequestTooltipUpdate,
-    ModifyTree(Box<dyn FnOnce(&mut Tree, &mut context::EventContext) + 'static>),
+    ModifyTree(TreeModifier),
     Screenshot,
+    #[cfg(not(target_arch = "wasm32"))]
+    ExportImage(renderer::ImageOptions),
     Save,
+    SaveAs,
     Load,
-    Export,
+    #[cfg(not(target_arch = "wasm32"))]
+    Open(PathBuf),
+    LoadSource,
+    Export(Language),
+    ExportDiagram(Diagram),
+    Undo,
+    Redo,
     SetTool(Tool),
 }
 
@@ -48,14 +69,34 @@ impl fmt::Debug for AppUserEvent {
             AppUserEvent::RequestTooltipUpdate => f.write_str("RequestTooltipUpdate"),
             AppUserEvent::ModifyTree(_) => f.write_str("ModifyTree"),
             AppUserEvent::Screenshot => f.write_str("Screenshot"),
+            #[cfg(not(target_arch = "wasm32"))]
+            AppUserEvent::ExportImage(options) => {
+                f.debug_tuple("ExportImage").field(options).finish()
+            }
             AppUserEvent::Save => f.write_str("Save"),
-            AppUserEvent::Export => f.write_str("Export"),
+            AppUserEvent::SaveAs => f.write_str("SaveAs"),
+            AppUserEvent::Export(language) => f.debug_tuple("Export").field(language).finish(),
+            AppUserEvent::ExportDiagram(diagram) => {
+                f.debug_tuple("ExportDiagram").field(diagram).finish()
+            }
             AppUserEvent::Load => f.write_str("Load"),
+            #[cfg(not(target_arch = "wasm32"))]
+            AppUserEvent::Open(path) => f.debug_tuple("Open").field(path).finish(),
+            AppUserEvent::LoadSource => f.write_str("LoadSource"),
+            AppUserEvent::Undo => f.write_str("Undo"),
+            AppUserEvent::Redo => f.write_str("Redo"),
             AppUserEvent::SetTool(tool) => f.debug_tuple("SetTool").field(tool).finish(),
         }
     }
 }
 
+/// The file the project was loaded from (or last saved to)
+struct Document {
+    /// On the web, this is only the name of the file
+    path: PathBuf,
+    format: Format,
+}
+
 pub struct App<'s> {
     pub window: renderer::WindowRenderer<'s>,
     #[cfg(not(target_arch = "wasm32"))]
@@ -65,6 +106,9 @@ pub struct App<'s> {
 
     pub tree: Tree,
     pub project: Project,
+    document: Option<Document>,
+
+    autosave: Autosave,
 }
 
 impl App<'_> {
@@ -74,12 +118,11 @@ impl App<'_> {
         let mut window = renderer::WindowRenderer::new(vello_render_context.clone());
 
         #[cfg(not(target_arch = "wasm32"))]
-        let png = renderer::PngRenderer::new(vello_render_context)
-            .await
-            .expect("Couldn't create PNG renderer");
+        let png = renderer::PngRenderer::new(vello_render_context).await;
 
         let mut state = State::new(event_loop);
         let mut project = Project::new(String::new());
+        state.history.reset(&project);
 
         // Can't use the macro here, because App doesn't exist yet
         let tree = Tree::new(&mut context::EventContext {
@@ -97,6 +140,9 @@ impl App<'_> {
 
             tree,
             project,
+            document: None,
+
+            autosave: Autosave::new(),
         }
     }
 
@@ -111,23 +157,270 @@ impl App<'_> {
         self.window.render().unwrap();
     }
 
-    pub fn load_project(&mut self, data: Vec<u8>, name: &str) {
-        let extension = name.split('.').last().unwrap_or_default();
+    /// Loads a project (or imports a source file). Project files become the current document.
+    pub fn load_project(&mut self, data: Vec<u8>, path: PathBuf) {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+
+        match import::from_bytes(&data, &name) {
+            Ok(project) => {
+                self.set_project(project);
+
+                // Failing to update the list isn't worth bothering the user with
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    _ = self.state.recent.add(&path);
+                }
+
+                let extension = path.extension().and_then(|ext| ext.to_str());
+                self.document = extension
+                    .and_then(Format::from_extension)
+                    .map(|format| Document { path, format });
+            }
+            Err(error) => {
+                let error = FileError::new(Operation::Open, name, error);
+                self.state.report_error(error);
+            }
+        }
+    }
+
+    /// Opens a project file or imports a source file (or a directory of them)
+    #[cfg(not(target_arch = "wasm32"))]
+    fn open_file(&mut self, path: PathBuf) {
+        let result = if path.is_dir() {
+            import::from_directory(&path).map(|project| self.set_project(project))
+        } else {
+            std::fs::read(&path)
+                .map(|data| self.load_project(data, path.clone()))
+                .map_err(|e| e.into())
+        };
+
+        if let Err(error) = result {
+            // It was probably moved or deleted, so there's no point in offering it again
+            _ = self.state.recent.remove(&path);
+
+            self.state
+                .report_error(FileError::from_path(Operation::Open, &path, error));
+        }
+    }
+
+    /// Writes the data into the file, showing an error if it fails
+    #[cfg(not(target_arch = "wasm32"))]
+    fn write_file(&mut self, operation: Operation, path: &Path, data: impl AsRef<[u8]>) -> bool {
+        if let Err(error) = std::fs::write(path, data) {
+            self.state
+                .report_error(FileError::from_path(operation, path, error));
+
+            return false;
+        }
+
+        true
+    }
+
+    /// Saves the project into the current document.
+    /// Asks for a new file if there's no document yet, or if `prompt` is set.
+    ///
+    /// Returns true if the project was saved.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn save(&mut self, prompt: bool) -> bool {
+        let (path, format) = match &self.document {
+            Some(Document { path, format }) if !prompt => (path.clone(), *format),
+            document => {
+                let dialog = FileDialog::new()
+                    .add_filter("binary", &["bin"])
+                    .add_filter("json", &["json"]);
+
+                let dialog = match document.as_ref().and_then(|doc| doc.path.parent()) {
+                    Some(directory) => dialog.set_directory(directory),
+                    None => dialog,
+                };
+
+                let name = match document {
+                    Some(document) => document.path.file_name().unwrap().to_string_lossy().into(),
+                    None => {
+                        let name = self.project.get_sanitized_name().to_lowercase();
+                        name + "." + Format::Binary.extension()
+                    }
+                };
+
+                let Some(path) = dialog.set_file_name(name).save_file() else {
+                    return false;
+                };
+
+                let format = path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .and_then(Format::from_extension)
+                    .unwrap_or(Format::Binary);
+
+                (path, format)
+            }
+        };
+
+        self.state.history.commit(&self.project);
+
+        if !self.write_file(Operation::Save, &path, format.serialize(&self.project)) {
+            return false;
+        }
+
+        _ = self.state.recent.add(&path);
+        self.document = Some(Document { path, format });
+        self.state.history.mark_saved();
+
+        true
+    }
+
+    /// Downloads the project, using the name and format of the current document.
+    /// Without a document (or with `prompt` set), the shift key decides between json and binary.
+    #[cfg(target_arch = "wasm32")]
+    fn save(&mut self, prompt: bool) -> bool {
+        let (path, format) = match &self.document {
+            Some(Document { path, format }) if !prompt => (path.clone(), *format),
+            _ => {
+                let format = if self.state.modifiers.shift_key() {
+                    Format::Json
+                } else {
+                    Format::Binary
+                };
+
+                let name = self.project.get_sanitized_name().to_lowercase();
+                (PathBuf::from(name + "." + format.extension()), format)
+            }
+        };
+
+        self.state.history.commit(&self.project);
+
+        let data = format.serialize(&self.project);
+        crate::web::download_bytes(&data, &path.to_string_lossy());
+
+        self.document = Some(Document { path, format });
+        self.state.history.mark_saved();
+
+        true
+    }
+
+    /// Asks whether the unsaved changes should be saved before closing.
+    ///
+    /// Returns false if closing was cancelled.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn confirm_close(&mut self) -> bool {
+        self.state.history.commit(&self.project);
+
+        if !self.state.history.is_modified() {
+            return true;
+        }
+
+        let result = MessageDialog::new()
+            .set_title("Unsaved changes")
+            .set_description(format!(
+                "Do you want to save the changes to {} before closing?",
+                self.title_name()
+            ))
+            .set_buttons(MessageButtons::YesNoCancel)
+            .show();
+
+        match result {
+            MessageDialogResult::Yes => self.save(false),
+            MessageDialogResult::No => true,
+            _ => false,
+        }
+    }
+
+    fn title_name(&self) -> &str {
+        if self.project.name.is_empty() {
+            "Untitled"
+        } else {
+            &self.project.name
+        }
+    }
+
+    /// Shows the project name in the window title, with a marker if it has unsaved changes
+    fn update_title(&self) {
+        let Some(window) = &self.window.window else {
+            return;
+        };
+
+        let marker = if self.state.history.is_modified() {
+            "*"
+        } else {
+            ""
+        };
+
+        let title = format!("{}{marker} - UML Editor", self.title_name());
+
+        if window.title() != title {
+            window.set_title(&title);
+        }
+    }
 
-        let Some(project) = (match extension {
-            "json" => serde_json::from_slice(&data).ok(),
-            _ => postcard::from_bytes(&data).ok(),
-        }) else {
+    /// Offers to restore the project from the last session, if it didn't end cleanly
+    fn restore_session(&mut self) {
+        let Some((project, view)) = Autosave::load() else {
             return;
         };
 
+        const MESSAGE: &str =
+            "The app wasn't closed properly last time. Do you want to restore the unsaved project?";
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let restore = MessageDialog::new()
+            .set_title("Restore the last session")
+            .set_description(MESSAGE)
+            .set_buttons(MessageButtons::YesNo)
+            .show()
+            == MessageDialogResult::Yes;
+
+        #[cfg(target_arch = "wasm32")]
+        let restore = crate::web::confirm(MESSAGE);
+
+        if restore {
+            self.set_project(project);
+            self.state.history.mark_unsaved();
+            self.state.workspace.restore = Some(view);
+        } else {
+            self.autosave.clear();
+        }
+    }
+
+    pub fn set_project(&mut self, project: Project) {
         self.project = project;
-        self.state.selected_entity = None;
+        self.document = None;
+        self.state.history.reset(&self.project);
+        self.state.select(None);
+        self.window.request_redraw();
+    }
+
+    /// Walk the undo history in the given direction
+    pub fn undo(&mut self, redo: bool) {
+        let changed = if redo {
+            self.state.history.redo(&mut self.project)
+        } else {
+            self.state.history.undo(&mut self.project)
+        };
+
+        if !changed {
+            return;
+        }
+
+        // The selected entities might not exist anymore
+        let selection = self
+            .state
+            .selection
+            .iter()
+            .copied()
+            .filter(|&e| self.project.entities.contains_key(e))
+            .collect::<Vec<_>>();
+
+        self.state.set_selection(selection);
+
+        self.state.selected_point = None;
         self.window.request_redraw();
     }
 
     pub fn update_cursor(&mut self) {
-        let cursor = self.tree.cursor(ctx!(self)).unwrap_or(CursorIcon::Default);
+        let cursor = self
+            .tree
+            .cursor(ctx!(ref self))
+            .unwrap_or(CursorIcon::Default);
         self.window.set_cursor(cursor);
     }
 }
@@ -137,6 +430,7 @@ impl ApplicationHandler<AppUserEvent> for App<'_> {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         if self.window.window.is_none() {
             pollster::block_on(self.window.init(event_loop)).unwrap();
+            self.restore_session();
         }
     }
 
@@ -144,10 +438,26 @@ impl ApplicationHandler<AppUserEvent> for App<'_> {
     fn resumed(&mut self, _event_loop: &ActiveEventLoop) {
         use crate::web;
 
-        // macOS keybind setup + scroll event + file picker
+        // macOS keybind setup + scroll event + file picker + drag and drop
         self.state.use_super = web::use_super();
         web::setup_scroll_event(self.state.event_loop.clone());
         web::setup_file_picker(self.state.event_loop.clone());
+        web::setup_drop_target(self.state.event_loop.clone());
+
+        self.restore_session();
+    }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        let view = self.state.workspace.view;
+
+        if let Err(error) = self.autosave.tick(&self.project, view) {
+            let error = FileError::new(Operation::Save, Autosave::NAME, error);
+            self.state.report_error(error);
+        }
+
+        event_loop.set_control_flow(ControlFlow::WaitUntil(self.autosave.deadline()));
+
+        self.update_title();
     }
 
     fn user_event(&mut self, _event_loop: &ActiveEventLoop, event: AppUserEvent) {
@@ -165,13 +475,13 @@ impl ApplicationHandler<AppUserEvent> for App<'_> {
             }
             #[cfg(target_arch = "wasm32")]
             AppUserEvent::FileLoaded(data, name) => {
-                self.load_project(data, &name);
+                self.load_project(data, PathBuf::from(name));
             }
 
             AppUserEvent::RequestRedraw => self.window.request_redraw(),
             AppUserEvent::RequestCursorUpdate => self.update_cursor(),
             AppUserEvent::RequestTooltipUpdate => {
-                self.state.tooltip_state = self.tree.tooltip(ctx!());
+                self.state.tooltip_state = self.tree.tooltip(super::ctx!(ref self));
                 self.window.request_redraw();
             }
             AppUserEvent::ModifyTree(f) => {
@@ -195,7 +505,14 @@ impl ApplicationHandler<AppUserEvent> for App<'_> {
                     state: &self.state,
                 });
 
-                let image = self.png.render().unwrap();
+                // Screenshots of the whole window need the GPU
+                let image = match self.png.render() {
+                    Ok(image) => image,
+                    Err(error) => {
+                        let error = FileError::new(Operation::Export, "screenshot.png", error);
+                        return self.state.report_error(error);
+                    }
+                };
 
                 // Save the image
                 let Some(path) = FileDialog::new()
@@ -206,159 +523,225 @@ impl ApplicationHandler<AppUserEvent> for App<'_> {
                     return;
                 };
 
-                _ = std::fs::write(path, image);
+                self.write_file(Operation::Export, &path, image);
             }
             #[cfg(target_arch = "wasm32")]
             AppUserEvent::Screenshot => {
                 crate::web::screenshot(self.window.window.as_ref().unwrap());
             }
             #[cfg(not(target_arch = "wasm32"))]
-            AppUserEvent::Save => {
+            AppUserEvent::ExportImage(options) => {
+                let name = self.project.get_sanitized_name() + ".png";
+                let colors = self.window.canvas.colors();
+
+                let image = match self.png.render_diagram(&self.project, options, colors) {
+                    Ok(image) => image,
+                    Err(error) => {
+                        let error = FileError::new(Operation::Export, name, error);
+                        return self.state.report_error(error);
+                    }
+                };
+
                 let Some(path) = FileDialog::new()
-                    .add_filter("binary", &["bin"])
-                    .add_filter("json", &["json"])
-                    .set_file_name(self.project.get_sanitized_name().to_lowercase() + ".bin")
+                    .add_filter("png", &["png"])
+                    .set_file_name(name)
                     .save_file()
                 else {
                     return;
                 };
 
-                let extension = path.extension().and_then(|ext| ext.to_str());
-
-                let data = match extension {
-                    Some("json") => serde_json::to_vec_pretty(&self.project).unwrap(),
-                    _ => postcard::to_stdvec(&self.project).unwrap(),
-                };
-
-                _ = std::fs::write(path, data);
+                self.write_file(Operation::Export, &path, image);
             }
-            #[cfg(target_arch = "wasm32")]
             AppUserEvent::Save => {
-                let json = self.state.modifiers.shift_key();
-
-                let mut name = self.project.get_sanitized_name().to_lowercase();
-                let data = if json {
-                    name += ".json";
-
-                    serde_json::to_vec_pretty(&self.project).unwrap()
-                } else {
-                    name += ".bin";
-
-                    postcard::to_stdvec(&self.project).unwrap()
-                };
-
-                crate::web::download_bytes(&data, &name);
+                self.save(false);
+            }
+            AppUserEvent::SaveAs => {
+                self.save(true);
             }
             #[cfg(not(target_arch = "wasm32"))]
             AppUserEvent::Load => {
-                let Some(path) = FileDialog::new()
+                let dialog = FileDialog::new()
                     .add_filter("binary", &["bin"])
                     .add_filter("json", &["json"])
-                    .set_directory("data")
-                    .set_file_name("project.bin")
-                    .pick_file()
-                else {
-                    return;
+                    .add_filter("plantuml", &["puml", "plantuml"]);
+
+                // Start next to the current document
+                let dialog = match self.document.as_ref().and_then(|doc| doc.path.parent()) {
+                    Some(directory) => dialog.set_directory(directory),
+                    None => dialog,
                 };
 
-                if let Ok(data) = std::fs::read(&path) {
-                    self.load_project(data, path.file_name().unwrap().to_str().unwrap());
+                if let Some(path) = dialog.pick_file() {
+                    self.open_file(path);
                 }
             }
+            #[cfg(not(target_arch = "wasm32"))]
+            AppUserEvent::Open(path) => self.open_file(path),
             #[cfg(target_arch = "wasm32")]
             AppUserEvent::Load => {
-                crate::web::open_file_picker();
+                crate::web::open_file_picker(
+                    ".bin,application/octet-stream,.json,application/json,.puml,.plantuml",
+                );
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            AppUserEvent::LoadSource => {
+                let directory = "Directory".to_string();
+                let archive = "Zip archive".to_string();
+
+                let result = MessageDialog::new()
+                    .set_title("Load from source")
+                    .set_description("Load the C# source files from a directory or a zip archive?")
+                    .set_buttons(MessageButtons::YesNoCancelCustom(
+                        directory.clone(),
+                        archive.clone(),
+                        "Cancel".to_string(),
+                    ))
+                    .show();
+
+                let (path, project) = match result {
+                    MessageDialogResult::Custom(choice) if choice == directory => {
+                        let Some(path) = FileDialog::new().pick_folder() else {
+                            return;
+                        };
+
+                        let project = import::from_directory(&path);
+                        (path, project)
+                    }
+                    MessageDialogResult::Custom(choice) if choice == archive => {
+                        let Some(path) = FileDialog::new().add_filter("zip", &["zip"]).pick_file()
+                        else {
+                            return;
+                        };
+
+                        let stem = path
+                            .file_stem()
+                            .and_then(|s| s.to_str())
+                            .unwrap_or_default();
+                        let project = std::fs::read(&path)
+                            .map_err(|e| e.into())
+                            .and_then(|data| import::from_zip(&data, stem));
+
+                        (path, project)
+                    }
+                    _ => return,
+                };
+
+                match project {
+                    Ok(project) => self.set_project(project),
+                    Err(error) => {
+                        self.state
+                            .report_error(FileError::from_path(Operation::Open, &path, error))
+                    }
+                }
+            }
+            #[cfg(target_arch = "wasm32")]
+            AppUserEvent::LoadSource => {
+                crate::web::open_file_picker(".zip,application/zip,.cs");
             }
+            AppUserEvent::Undo => self.undo(false),
+            AppUserEvent::Redo => self.undo(true),
             AppUserEvent::SetTool(tool) => {
                 self.state.tool = tool;
 
-                // Interfaces can't have parent relations, so deselect the entity if the tool parent is used
+                // Interfaces and enums can't have parent relations (and enums can't implement interfaces),
+                // so deselect the entity if it can't be the source of the relation
                 if self
                     .state
                     .selected_entity
                     .and_then(|e| self.project.entities.get(e))
-                    .is_some_and(|e| e.entity_type == EntityType::Interface)
-                    && tool == Tool::Parent
+                    .is_some_and(|e| match tool {
+                        Tool::Parent => !e.entity_type.can_inherit(),
+                        Tool::Implementation => !e.entity_type.can_implement(),
+                        _ => false,
+                    })
                 {
-                    self.state.selected_entity = None;
+                    self.state.select(None);
                 }
 
                 self.update_cursor();
                 self.window.request_redraw();
             }
-            AppUserEvent::Export => {
+            AppUserEvent::Export(language) => {
                 let name = self.project.get_sanitized_name();
 
-                #[allow(unused_mut)]
-                let mut file;
-                let mut zip;
+                // The generated code wouldn't compile, show the problems instead
+                let errors = validation::validate(&self.project)
+                    .iter()
+                    .filter(|d| d.severity == Severity::Error)
+                    .count();
+
+                if errors > 0 {
+                    let reason = format!(
+                        "The diagram has {}, see the problems",
+                        Severity::Error.count(errors)
+                    );
+
+                    self.state.problems.expanded = true;
+                    self.state.report_error(FileError::new(
+                        Operation::Export,
+                        name + ".zip",
+                        reason,
+                    ));
+
+                    return;
+                }
+
+                let files = language.generator().generate(&self.project);
 
                 #[cfg(not(target_arch = "wasm32"))]
                 {
                     let Some(path) = FileDialog::new()
                         .add_filter("zip", &["zip"])
-                        .set_file_name(name.clone() + ".zip")
+                        .set_file_name(name + ".zip")
                         .save_file()
                     else {
                         return;
                     };
 
-                    file = std::fs::File::create(&path).unwrap();
-                    zip = ZipWriter::new(file);
+                    let result = std::fs::File::create(&path)
+                        .map_err(|e| e.into())
+                        .and_then(|file| codegen::write_zip(&files, file));
+
+                    if let Err(error) = result {
+                        self.state.report_error(FileError::from_path(
+                            Operation::Export,
+                            &path,
+                            error,
+                        ));
+                    }
                 }
                 #[cfg(target_arch = "wasm32")]
                 {
-                    file = Vec::<u8>::new();
-                    zip = ZipWriter::new(std::io::Cursor::new(&mut file));
+                    let name = name + ".zip";
+
+                    match codegen::write_zip(&files, std::io::Cursor::new(Vec::new())) {
+                        Ok(data) => crate::web::download_bytes(&data.into_inner(), &name),
+                        Err(error) => {
+                            self.state
+                                .report_error(FileError::new(Operation::Export, name, error))
+                        }
+                    }
                 }
+            }
+            AppUserEvent::ExportDiagram(diagram) => {
+                let name = self.project.get_sanitized_name() + "." + diagram.extension();
+                let text = diagram.serialize(&self.project);
 
-                let options: FileOptions<()> = FileOptions::default()
-                    .compression_method(zip::CompressionMethod::Deflated)
-                    .unix_permissions(0o755);
-
-                zip.add_directory(&name, options).unwrap();
-
-                // Write project file
-                let proj = xml!(
-                    <?xml version="1.0"?>
-                    <Project Sdk="Microsoft.NET.Sdk">
-                      <PropertyGroup>
-                        <TargetFramework>"net8.0"</>
-                        <ImplicitUsings>"enable"</>
-                        <Nullable>"enable"</>
-                      </>
-                    </>
-                );
-
-                let project_file = format!("{name}/{name}.csproj");
-                zip.start_file(project_file.clone(), options).unwrap();
-                writeln!(zip, "{}", proj.as_str()).unwrap();
-
-                // Write solution file
-                let sln = xml!(
-                    <?xml version="1.0"?>
-                    <Solution>
-                      <Project Path={project_file} />
-                    </>
-                );
-
-                zip.start_file(format!("{name}.slnx"), options).unwrap();
-                writeln!(zip, "{}", sln.as_str()).unwrap();
-
-                // Write entities
-                for (key, entity) in &self.project.entities {
-                    let file_name = format!("{name}/{}.cs", self.project.sanitize(&entity.name));
-                    zip.start_file(file_name, options).unwrap();
-                    let tokens = self.project.entity_to_token(key);
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    let Some(path) = FileDialog::new()
+                        .add_filter(diagram.to_string(), &[diagram.extension()])
+                        .set_file_name(name)
+                        .save_file()
+                    else {
+                        return;
+                    };
 
-                    writeln!(zip, "{}", TokenVec(tokens)).unwrap();
+                    self.write_file(Operation::Export, &path, text);
                 }
-
-                zip.finish().unwrap();
-
                 #[cfg(target_arch = "wasm32")]
                 {
-                    crate::web::download_bytes(&file, &(name + ".zip"));
+                    crate::web::download_bytes(text.as_bytes(), &name);
                 }
             }
         }
@@ -385,7 +768,17 @@ impl ApplicationHandler<AppUserEvent> for App<'_> {
         }
 
         match event {
-            WindowEvent::CloseRequested => event_loop.exit(),
+            WindowEvent::CloseRequested => {
+                #[cfg(not(target_arch = "wasm32"))]
+                if !self.confirm_close() {
+                    return;
+                }
+
+                self.autosave.clear();
+                event_loop.exit();
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            WindowEvent::DroppedFile(path) => self.open_file(path),
             WindowEvent::Resized(size) => self.window.resize(size),
             WindowEvent::RedrawRequested => self.redraw(),
             WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
@@ -470,9 +863,27 @@ impl ApplicationHandler<AppUserEvent> for App<'_> {
                     self.state.mouse_buttons.remove(&button);
                     self.tree.on_mouseup(ctx!(), button);
                 }
+
+                self.state.history.commit(&self.project);
             }
 
             WindowEvent::KeyboardInput { event, .. } => {
+                // Ctrl+S saves in place, Ctrl+Shift+S asks for a new file (even while typing)
+                if let Key::Character(ch) = &event.logical_key {
+                    if event.state == ElementState::Pressed
+                        && self.state.main_modifier()
+                        && ch.eq_ignore_ascii_case("s")
+                    {
+                        if self.state.modifiers.shift_key() {
+                            self.state.save_as();
+                        } else {
+                            self.state.save();
+                        }
+
+                        return;
+                    }
+                }
+
                 if event.state == ElementState::Pressed {
                     self.state.keys.insert(event.logical_key.clone());
                     self.tree.on_keydown(ctx!(), event);
@@ -480,6 +891,8 @@ impl ApplicationHandler<AppUserEvent> for App<'_> {
                     self.state.keys.remove(&event.logical_key);
                     self.tree.on_keyup(ctx!(), event);
                 }
+
+                self.state.history.commit(&self.project);
             }
 
             WindowEvent::ModifiersChanged(modifiers) => self.state.modifiers = modifiers.state(),
@@ -0,0 +1,94 @@
+//! Periodic backup of the open project, so it can be restored after a crash.
+//!
+//! The session is removed when the app is closed normally, so it only exists if the last one didn't end cleanly.
+
+use super::storage;
+use crate::data::{file, Project};
+use crate::elements::workspace::WorkspaceView;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::error::Error;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+#[cfg(target_arch = "wasm32")]
+use web_time::{Duration, Instant};
+
+#[derive(Serialize, Deserialize)]
+struct Session {
+    view: WorkspaceView,
+
+    /// The project in the (versioned) JSON format
+    project: Value,
+}
+
+pub struct Autosave {
+    last: Instant,
+
+    /// The last stored session, used to skip saving if nothing changed
+    saved: Option<String>,
+}
+
+impl Autosave {
+    /// How often the project is saved
+    const INTERVAL: Duration = Duration::from_secs(30);
+
+    /// Name of the stored session
+    pub const NAME: &str = "autosave.json";
+
+    pub fn new() -> Self {
+        Self {
+            last: Instant::now(),
+            saved: None,
+        }
+    }
+
+    /// When the next save should happen
+    #[inline]
+    pub fn deadline(&self) -> Instant {
+        self.last + Self::INTERVAL
+    }
+
+    /// Saves the session if the interval has passed since the last save
+    pub fn tick(&mut self, project: &Project, view: WorkspaceView) -> Result<(), Box<dyn Error>> {
+        if Instant::now() < self.deadline() {
+            return Ok(());
+        }
+
+        self.last = Instant::now();
+
+        // An empty project isn't worth restoring
+        if project.entities.is_empty() {
+            self.clear();
+            return Ok(());
+        }
+
+        let session = serde_json::to_string(&Session {
+            view,
+            project: file::to_value(project),
+        })?;
+
+        if self.saved.as_ref() == Some(&session) {
+            return Ok(());
+        }
+
+        // Even if it fails, the same session isn't written (and reported) again until something changes
+        let result = storage::write(Self::NAME, &session);
+        self.saved = Some(session);
+
+        result
+    }
+
+    /// Loads the session left behind by a previous run of the app
+    pub fn load() -> Option<(Project, WorkspaceView)> {
+        let session: Session = serde_json::from_str(&storage::read(Self::NAME)?).ok()?;
+        let project = file::from_value(session.project).ok()?;
+
+        Some((project, session.view))
+    }
+
+    /// Removes the stored session (e.g. when the app is closed normally)
+    pub fn clear(&mut self) {
+        self.saved = None;
+        storage::remove(Self::NAME);
+    }
+}
@@ -41,6 +41,14 @@ macro_rules! ctx {
             c: $ctx.c,
         }
     };
+    // Read-only context for getters
+    (ref $app:expr) => {
+        &$crate::app::context::GetterContext {
+            project: &$app.project,
+            state: &$app.state,
+            c: &$app.window.canvas,
+        }
+    };
     ($app:expr) => {
         &mut $crate::app::context::MutContext {
             project: &mut $app.project,
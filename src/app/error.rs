@@ -0,0 +1,64 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operation {
+    Open,
+    Save,
+    Export,
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operation::Open => write!(f, "open"),
+            Operation::Save => write!(f, "save"),
+            Operation::Export => write!(f, "export"),
+        }
+    }
+}
+
+/// A failed file operation (loading, saving or exporting), shown to the user in a toast
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileError {
+    pub operation: Operation,
+
+    /// Name of the file (without the directory)
+    pub file: String,
+    pub reason: String,
+}
+
+impl FileError {
+    pub fn new(operation: Operation, file: impl Into<String>, reason: impl Display) -> Self {
+        Self {
+            operation,
+            file: file.into(),
+            reason: reason.to_string(),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_path(operation: Operation, path: &Path, reason: impl Display) -> Self {
+        let file = path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_else(|| path.to_string_lossy());
+
+        Self::new(operation, file, reason)
+    }
+
+    /// Short summary of what failed (e.g. "Couldn't save project.bin")
+    pub fn title(&self) -> String {
+        format!("Couldn't {} {}", self.operation, self.file)
+    }
+}
+
+impl Display for FileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.title(), self.reason)
+    }
+}
+
+impl Error for FileError {}
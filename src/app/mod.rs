@@ -1,8 +1,14 @@
+#[allow(clippy::module_inception)]
 pub mod app;
+pub mod autosave;
 pub mod context;
+pub mod error;
 pub mod event_target;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod recent;
 pub mod renderer;
 pub mod state;
+pub mod storage;
 pub mod tree;
 pub mod viewport;
 
@@ -0,0 +1,53 @@
+use super::storage;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+/// The most recently opened (or saved) files, newest first
+pub struct RecentFiles {
+    paths: Vec<PathBuf>,
+}
+
+impl RecentFiles {
+    /// How many files are remembered
+    const LIMIT: usize = 10;
+
+    /// Name of the stored list
+    const NAME: &str = "recent.json";
+
+    /// Loads the list stored by the previous runs of the app
+    pub fn load() -> Self {
+        let paths = storage::read(Self::NAME)
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        Self { paths }
+    }
+
+    #[inline]
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    /// Moves the file to the top of the list
+    pub fn add(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        // Relative paths would point somewhere else the next time the app is started
+        let path = std::path::absolute(path)?;
+
+        self.paths.retain(|p| *p != path);
+        self.paths.insert(0, path);
+        self.paths.truncate(Self::LIMIT);
+
+        self.persist()
+    }
+
+    /// Forgets the file (e.g. when it no longer exists)
+    pub fn remove(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        self.paths.retain(|p| p != path);
+
+        self.persist()
+    }
+
+    fn persist(&self) -> Result<(), Box<dyn Error>> {
+        storage::write(Self::NAME, &serde_json::to_string(&self.paths)?)
+    }
+}
@@ -0,0 +1,155 @@
+use super::png::{ImageOptions, PngRenderer};
+use crate::data::diagram::painter::{self, text_outline, Painter, TextStyle, DASHES};
+use crate::data::Project;
+use crate::geometry::{Point, Rect};
+use crate::presentation::Colors;
+use std::error::Error;
+use tiny_skia::{
+    FillRule, LineCap, LineJoin, Paint, PathBuilder, Pixmap, Stroke, StrokeDash, Transform,
+};
+use vello::kurbo::{BezPath, PathEl, RoundedRect, Shape};
+use vello::peniko::Color;
+
+/// Software rasterizer used for exporting the diagram on machines without a GPU
+struct Cpu {
+    pixmap: Pixmap,
+    transform: Transform,
+}
+
+fn path(path: &BezPath) -> Option<tiny_skia::Path> {
+    let mut builder = PathBuilder::new();
+
+    for element in path.elements() {
+        match *element {
+            PathEl::MoveTo(p) => builder.move_to(p.x as f32, p.y as f32),
+            PathEl::LineTo(p) => builder.line_to(p.x as f32, p.y as f32),
+            PathEl::QuadTo(c, p) => builder.quad_to(c.x as f32, c.y as f32, p.x as f32, p.y as f32),
+            PathEl::CurveTo(c0, c1, p) => builder.cubic_to(
+                c0.x as f32,
+                c0.y as f32,
+                c1.x as f32,
+                c1.y as f32,
+                p.x as f32,
+                p.y as f32,
+            ),
+            PathEl::ClosePath => builder.close(),
+        }
+    }
+
+    builder.finish()
+}
+
+fn paint(color: Color) -> Paint<'static> {
+    let color = color.to_rgba8();
+
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(color.r, color.g, color.b, color.a);
+    paint.anti_alias = true;
+
+    paint
+}
+
+impl Painter for Cpu {
+    fn rect(&mut self, rect: Rect, radius: f64, color: Color, shadow: Option<Color>) {
+        // There's no blur, so the shadow is approximated by a few growing boxes that fade out
+        if let Some(shadow) = shadow {
+            for spread in [1., 2., 3., 4.] {
+                let rect = rect.translate((0., 1.)).inset_uniform(-spread);
+                let shape = RoundedRect::from_rect(rect.into(), radius + spread).to_path(0.1);
+
+                self.fill(&shape, shadow.multiply_alpha(0.25));
+            }
+        }
+
+        self.fill(
+            &RoundedRect::from_rect(rect.into(), radius).to_path(0.1),
+            color,
+        );
+    }
+
+    fn text(&mut self, text: &str, origin: Point, style: TextStyle, color: Color) {
+        let outline = text_outline(text, style.font(), style.size, origin);
+        self.fill(&outline, color);
+    }
+
+    fn fill(&mut self, path: &BezPath, color: Color) {
+        if let Some(path) = self::path(path) {
+            self.pixmap.fill_path(
+                &path,
+                &paint(color),
+                FillRule::Winding,
+                self.transform,
+                None,
+            );
+        }
+    }
+
+    fn stroke(&mut self, path: &BezPath, color: Color, width: f64, dashed: bool) {
+        let Some(path) = self::path(path) else {
+            return;
+        };
+
+        let stroke = Stroke {
+            width: width as f32,
+            line_cap: if dashed {
+                LineCap::Round
+            } else {
+                LineCap::Butt
+            },
+            line_join: LineJoin::Round,
+            dash: dashed
+                .then(|| StrokeDash::new(DASHES.map(|d| d as f32).to_vec(), 0.))
+                .flatten(),
+            ..Default::default()
+        };
+
+        self.pixmap
+            .stroke_path(&path, &paint(color), &stroke, self.transform, None);
+    }
+}
+
+/// Renders the whole diagram into a PNG image without using the GPU
+pub fn render_diagram(
+    project: &Project,
+    options: ImageOptions,
+    colors: &Colors,
+) -> Result<Vec<u8>, Box<dyn Error>> {
+    let bounds = painter::bounding_box(project).ok_or("The diagram is empty")?;
+
+    let view = bounds.inset_uniform(-options.padding);
+    let size = (view.size * options.scale).ceil();
+    let (width, height) = (size.x as u32, size.y as u32);
+    PngRenderer::buffer_size(width, height)?;
+
+    let mut pixmap = Pixmap::new(width, height).ok_or("The image is too large")?;
+
+    if !options.transparent {
+        let background = colors.workspace_background.to_rgba8();
+        pixmap.fill(tiny_skia::Color::from_rgba8(
+            background.r,
+            background.g,
+            background.b,
+            background.a,
+        ));
+    }
+
+    let scale = options.scale as f32;
+    let transform = Transform::from_translate(-view.origin.x as f32, -view.origin.y as f32)
+        .post_scale(scale, scale);
+
+    let mut cpu = Cpu { pixmap, transform };
+    painter::paint(project, colors, &mut cpu);
+
+    // The pixmap is premultiplied, but PNGs aren't
+    let data = cpu
+        .pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect::<Vec<_>>();
+
+    PngRenderer::encode(width, height, &data)
+}
@@ -2,12 +2,14 @@ use std::error::Error;
 
 pub mod canvas;
 #[cfg(not(target_arch = "wasm32"))]
+mod cpu;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod png;
 pub mod window;
 
 pub use canvas::Canvas;
 #[cfg(not(target_arch = "wasm32"))]
-pub use png::PngRenderer;
+pub use png::{ImageOptions, PngRenderer};
 pub use window::WindowRenderer;
 
 pub trait Renderer {
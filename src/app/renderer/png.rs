@@ -1,7 +1,11 @@
-use super::{Canvas, Renderer};
+use super::{cpu, Canvas, Renderer};
+use crate::data::{diagram::painter, Project};
+use crate::elements::workspace::Workspace;
+use crate::geometry::Point;
 use crate::presentation::Colors;
-use std::{cell::RefCell, num::NonZeroUsize, rc::Rc};
+use std::{cell::RefCell, error::Error, num::NonZeroUsize, rc::Rc};
 use vello::{
+    peniko::Color,
     util::{block_on_wgpu, RenderContext},
     wgpu::{
         self, BufferDescriptor, BufferUsages, Extent3d, ImageCopyBuffer, ImageDataLayout, MapMode,
@@ -13,9 +17,50 @@ use vello::{
 const WIDTH: u32 = 1200;
 const HEIGHT: u32 = 800;
 
+/// Upper bound for the size of a single tile (the actual limit depends on the device)
+const MAX_TILE_SIZE: u32 = 4096;
+
+/// Upper bound for the number of pixels in an exported image (1 GiB of RGBA data)
+const MAX_PIXELS: usize = 1 << 28;
+
+/// Options for exporting the whole diagram as an image
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageOptions {
+    /// Number of pixels per workspace pixel (e.g. 2x)
+    pub scale: f64,
+
+    /// Space around the diagram (in workspace pixels)
+    pub padding: f64,
+
+    /// Uses a transparent background instead of the theme's workspace background
+    pub transparent: bool,
+}
+
+impl ImageOptions {
+    pub const SCALES: [f64; 3] = [1., 2., 4.];
+
+    pub fn new(scale: f64) -> Self {
+        Self {
+            scale,
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for ImageOptions {
+    fn default() -> Self {
+        Self {
+            scale: 1.,
+            padding: 32.,
+            transparent: false,
+        }
+    }
+}
+
 // Heavily inspired by: https://github.com/linebender/vello/blob/8a84a4abf7aaabdb7de82e3d9d86ed427ad21638/examples/headless/src/main.rs
 
-pub struct PngRenderer {
+/// The GPU side of the renderer
+struct Gpu {
     // This needs to be shared with the window renderer
     context: Rc<RefCell<RenderContext>>,
 
@@ -24,15 +69,20 @@ pub struct PngRenderer {
 
     renderer: vello::Renderer,
     device: usize,
+}
+
+pub struct PngRenderer {
+    /// Missing when there's no compatible device, the diagram is then rendered on the CPU
+    gpu: Option<Gpu>,
+    tile_size: u32,
 
     canvas: Canvas,
 }
 
-impl PngRenderer {
-    /// Create a new PNG renderer.
-    ///
+impl Gpu {
     /// Mutably borrows the render context for the duration of the function.
-    pub async fn new(context: Rc<RefCell<RenderContext>>) -> Result<Self, vello::Error> {
+    #[allow(clippy::await_holding_refcell_ref)]
+    async fn new(context: Rc<RefCell<RenderContext>>) -> Result<Self, vello::Error> {
         let device = context
             .borrow_mut()
             .device(None)
@@ -49,6 +99,31 @@ impl PngRenderer {
             },
         )?;
 
+        Ok(Self {
+            context,
+            texture: None,
+            texture_view: None,
+            renderer,
+            device,
+        })
+    }
+}
+
+impl PngRenderer {
+    /// Create a new PNG renderer.
+    ///
+    /// Falls back to rendering on the CPU if there's no compatible GPU (only for the diagram export).
+    pub async fn new(context: Rc<RefCell<RenderContext>>) -> Self {
+        let gpu = Gpu::new(context).await.ok();
+
+        let tile_size = gpu.as_ref().map_or(MAX_TILE_SIZE, |gpu| {
+            gpu.context.borrow().devices[gpu.device]
+                .device
+                .limits()
+                .max_texture_dimension_2d
+                .min(MAX_TILE_SIZE)
+        });
+
         let canvas = Canvas {
             size: (WIDTH, HEIGHT),
             scale: 1.0,
@@ -56,14 +131,11 @@ impl PngRenderer {
             scene: Scene::new(),
         };
 
-        Ok(Self {
-            context,
-            texture: None,
-            texture_view: None,
-            renderer,
-            device,
+        Self {
+            gpu,
+            tile_size,
             canvas,
-        })
+        }
     }
 
     pub fn resize(&mut self, width: u32, height: u32) {
@@ -74,25 +146,111 @@ impl PngRenderer {
         self.canvas.size = (width, height);
 
         // Reset the reusables
-        self.texture = None;
-        self.texture_view = None;
+        if let Some(gpu) = &mut self.gpu {
+            gpu.texture = None;
+            gpu.texture_view = None;
+        }
     }
-}
 
-impl Renderer for PngRenderer {
-    type RenderOutput = Vec<u8>;
+    /// Renders the whole diagram (only the workspace items), cropped to its bounding box
+    pub fn render_diagram(
+        &mut self,
+        project: &Project,
+        options: ImageOptions,
+        colors: &'static Colors,
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        if self.gpu.is_none() {
+            return cpu::render_diagram(project, options, colors);
+        }
 
-    fn canvas(&mut self) -> &mut Canvas {
-        &mut self.canvas
+        // Same crop as the CPU fallback and the SVG export
+        let bounds = painter::bounding_box(project).ok_or("The diagram is empty")?;
+
+        let view = bounds.inset_uniform(-options.padding);
+        let size = (view.size * options.scale).ceil();
+
+        let background = if options.transparent {
+            Color::TRANSPARENT
+        } else {
+            colors.workspace_background
+        };
+
+        // Positioned so the diagram starts at the top-left corner
+        self.render_tiled(
+            (size.x as u32, size.y as u32),
+            options.scale,
+            colors,
+            background,
+            |c, origin| Workspace::detached(view.origin + origin, 1.).render_diagram(c, project),
+        )
     }
 
-    // Immutably borrows the context to get the device and queue
-    // Blocks the current thread until the gpu operations are done
-    fn render(&mut self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
-        let context = self.context.borrow();
+    /// Renders an image of any size by splitting it into tiles that fit into a texture.
+    ///
+    /// The size is in physical pixels. `draw` is called once for every tile
+    /// with the origin of the tile (in logical pixels, so divided by the scale).
+    fn render_tiled(
+        &mut self,
+        (width, height): (u32, u32),
+        scale: f64,
+        colors: &'static Colors,
+        background: Color,
+        mut draw: impl FnMut(&mut Canvas, Point),
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut image = vec![0; Self::buffer_size(width, height)?];
+
+        self.canvas.scale = scale;
+        self.canvas.colors = colors;
+
+        for y in (0..height).step_by(self.tile_size as usize) {
+            for x in (0..width).step_by(self.tile_size as usize) {
+                let tile_width = self.tile_size.min(width - x);
+                let tile_height = self.tile_size.min(height - y);
+                self.resize(tile_width, tile_height);
+
+                self.canvas.reset();
+                draw(&mut self.canvas, Point::new(x as f64, y as f64) / scale);
+
+                let tile = self.render_pixels(background)?;
+
+                // Copy the tile into the image row by row
+                for row in 0..tile_height {
+                    let source = row as usize * tile_width as usize * 4;
+                    let target = ((y + row) as usize * width as usize + x as usize) * 4;
+                    let length = tile_width as usize * 4;
+
+                    image[target..target + length].copy_from_slice(&tile[source..source + length]);
+                }
+            }
+        }
+
+        self.canvas.scale = 1.;
+        self.canvas.colors = &Colors::LIGHT;
 
-        let device = &context.devices[self.device].device;
-        let queue = &context.devices[self.device].queue;
+        Self::encode(width, height, &image)
+    }
+
+    /// The number of bytes needed for an RGBA image of the size
+    pub(super) fn buffer_size(width: u32, height: u32) -> Result<usize, Box<dyn Error>> {
+        let pixels = width as usize * height as usize;
+
+        if pixels > MAX_PIXELS {
+            return Err(format!("The image is too large ({width}×{height} pixels)").into());
+        }
+
+        Ok(pixels * 4)
+    }
+
+    /// Renders the canvas into raw RGBA pixels
+    ///
+    /// Immutably borrows the context to get the device and queue.
+    /// Blocks the current thread until the gpu operations are done.
+    fn render_pixels(&mut self, background: Color) -> Result<Vec<u8>, Box<dyn Error>> {
+        let gpu = self.gpu.as_mut().ok_or(vello::Error::NoCompatibleDevice)?;
+        let context = gpu.context.borrow();
+
+        let device = &context.devices[gpu.device].device;
+        let queue = &context.devices[gpu.device].queue;
 
         let (width, height) = self.canvas.size;
         let size = Extent3d {
@@ -102,7 +260,7 @@ impl Renderer for PngRenderer {
         };
 
         // Create the texture and view if they don't exist
-        let texture = self.texture.get_or_insert_with(|| {
+        let texture = gpu.texture.get_or_insert_with(|| {
             device.create_texture(&TextureDescriptor {
                 label: None,
                 size,
@@ -115,18 +273,18 @@ impl Renderer for PngRenderer {
             })
         });
 
-        let texture_view = self
+        let texture_view = gpu
             .texture_view
             .get_or_insert_with(|| texture.create_view(&Default::default()));
 
         // Render the scene to the texture
-        self.renderer.render_to_texture(
+        gpu.renderer.render_to_texture(
             device,
             queue,
             &self.canvas.scene,
             texture_view,
             &RenderParams {
-                base_color: Colors::LIGHT.workspace_background,
+                base_color: background,
                 width,
                 height,
                 antialiasing_method: AaConfig::Msaa16,
@@ -180,7 +338,11 @@ impl Renderer for PngRenderer {
             result_unpadded.extend(&data[start..start + (width * 4) as usize]);
         }
 
-        // Encode the image into a vec
+        Ok(result_unpadded)
+    }
+
+    /// Encodes the RGBA pixels into a PNG
+    pub(super) fn encode(width: u32, height: u32, data: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
         let mut png_data = Vec::new();
 
         let mut encoder = png::Encoder::new(&mut png_data, width, height);
@@ -188,9 +350,24 @@ impl Renderer for PngRenderer {
         encoder.set_depth(png::BitDepth::Eight);
 
         let mut writer = encoder.write_header()?;
-        writer.write_image_data(&result_unpadded)?;
+        writer.write_image_data(data)?;
         writer.finish()?;
 
         Ok(png_data)
     }
 }
+
+impl Renderer for PngRenderer {
+    type RenderOutput = Vec<u8>;
+
+    fn canvas(&mut self) -> &mut Canvas {
+        &mut self.canvas
+    }
+
+    fn render(&mut self) -> Result<Vec<u8>, Box<dyn Error>> {
+        let (width, height) = self.canvas.size;
+        let data = self.render_pixels(self.canvas.colors.workspace_background)?;
+
+        Self::encode(width, height, &data)
+    }
+}
@@ -58,6 +58,7 @@ impl WindowRenderer<'_> {
         }
     }
 
+    #[allow(clippy::await_holding_refcell_ref)]
     async fn setup(&mut self) -> Result<(), Box<dyn Error>> {
         let window = self.window.as_ref().unwrap();
 
@@ -1,12 +1,26 @@
 use super::context::EventContext;
+use super::error::FileError;
+#[cfg(not(target_arch = "wasm32"))]
+use super::recent::RecentFiles;
+#[cfg(not(target_arch = "wasm32"))]
+use super::renderer::ImageOptions;
 use super::{AppUserEvent, Tree};
+use crate::data::codegen::Language;
+use crate::data::diagram::Diagram;
 use crate::data::project::{ConnectionKey, EntityKey};
+use crate::data::History;
+use crate::elements::menu::{MenuItem, MenuState};
+use crate::elements::problems::ProblemsState;
 use crate::elements::sidebar::SidebarState;
 use crate::elements::toolbox_item::Tool;
 use crate::elements::tooltip::TooltipState;
+use crate::elements::workspace::WorkspaceState;
 use crate::geometry::Point;
 use clipboard::{ClipboardContext, ClipboardProvider};
+use indexmap::IndexSet;
 use std::collections::HashSet;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
 use taffy::NodeId;
 use winit::event::MouseButton;
 use winit::event_loop::EventLoopProxy;
@@ -34,12 +48,20 @@ pub struct State {
 
     // App state
     pub tool: Tool,
-    pub selected_entity: Option<EntityKey>,
+    pub selected_entity: Option<EntityKey>, // Only set when exactly one entity is selected
+    pub selection: IndexSet<EntityKey>,
     pub selected_point: Option<(ConnectionKey, usize)>,
     pub tooltip_state: Option<TooltipState>,
+    pub menu_state: Option<MenuState>,
+    pub error: Option<FileError>,
+    pub history: History,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub recent: RecentFiles,
 
     // Individual elements' state
     pub sidebar: SidebarState,
+    pub workspace: WorkspaceState,
+    pub problems: ProblemsState,
 }
 
 impl State {
@@ -63,10 +85,18 @@ impl State {
 
             tool: Tool::Select,
             tooltip_state: None,
+            menu_state: None,
+            error: None,
             selected_entity: None,
+            selection: IndexSet::new(),
             selected_point: None,
+            history: <_>::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            recent: RecentFiles::load(),
 
             sidebar: <_>::default(),
+            workspace: <_>::default(),
+            problems: <_>::default(),
         }
     }
 
@@ -102,6 +132,54 @@ impl State {
         return self.modifiers.control_key();
     }
 
+    /// Selects a single entity (or nothing), replacing the current selection
+    pub fn select(&mut self, entity: Option<EntityKey>) {
+        self.set_selection(entity);
+    }
+
+    /// Replaces the current selection
+    pub fn set_selection(&mut self, entities: impl IntoIterator<Item = EntityKey>) {
+        self.selection = entities.into_iter().collect();
+        self.update_selected_entity();
+    }
+
+    /// Adds the entity to the selection, or removes it if it's already selected
+    pub fn toggle_selection(&mut self, entity: EntityKey) {
+        if !self.selection.shift_remove(&entity) {
+            self.selection.insert(entity);
+        }
+
+        self.update_selected_entity();
+    }
+
+    #[inline]
+    pub fn is_selected(&self, entity: EntityKey) -> bool {
+        self.selection.contains(&entity)
+    }
+
+    fn update_selected_entity(&mut self) {
+        self.selected_entity = match self.selection.len() {
+            1 => self.selection.first().copied(),
+            _ => None,
+        };
+    }
+
+    /// Opens a popup menu at the cursor position
+    pub fn open_menu(&mut self, items: Vec<MenuItem>) {
+        self.menu_state = Some(MenuState {
+            origin: self.cursor,
+            items,
+        });
+
+        self.request_redraw();
+    }
+
+    /// Shows the error in a toast (replacing the previous one)
+    pub fn report_error(&mut self, error: FileError) {
+        self.error = Some(error);
+        self.request_redraw();
+    }
+
     #[inline]
     pub fn send_event(&self, event: AppUserEvent) {
         self.event_loop.send_event(event).unwrap();
@@ -132,19 +210,56 @@ impl State {
         self.send_event(AppUserEvent::Screenshot);
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    #[inline]
+    pub fn export_image(&self, options: ImageOptions) {
+        self.send_event(AppUserEvent::ExportImage(options));
+    }
+
     #[inline]
     pub fn save(&self) {
         self.send_event(AppUserEvent::Save);
     }
 
+    #[inline]
+    pub fn save_as(&self) {
+        self.send_event(AppUserEvent::SaveAs);
+    }
+
     #[inline]
     pub fn load(&self) {
         self.send_event(AppUserEvent::Load);
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    #[inline]
+    pub fn open(&self, path: PathBuf) {
+        self.send_event(AppUserEvent::Open(path));
+    }
+
+    #[inline]
+    pub fn load_source(&self) {
+        self.send_event(AppUserEvent::LoadSource);
+    }
+
+    #[inline]
+    pub fn export(&self, language: Language) {
+        self.send_event(AppUserEvent::Export(language));
+    }
+
+    #[inline]
+    pub fn export_diagram(&self, diagram: Diagram) {
+        self.send_event(AppUserEvent::ExportDiagram(diagram));
+    }
+
+    #[inline]
+    pub fn undo(&self) {
+        self.send_event(AppUserEvent::Undo);
+    }
+
     #[inline]
-    pub fn export(&self) {
-        self.send_event(AppUserEvent::Export);
+    pub fn redo(&self) {
+        self.send_event(AppUserEvent::Redo);
     }
 
     #[inline]
@@ -0,0 +1,54 @@
+//! Small files kept between the runs of the app (e.g. the autosave or the recent files).
+//!
+//! They're stored in the app data directory natively, and in `localStorage` on the web.
+
+use std::error::Error;
+
+#[cfg(not(target_arch = "wasm32"))]
+fn path(name: &str) -> Option<std::path::PathBuf> {
+    Some(dirs::data_local_dir()?.join("UML").join(name))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read(name: &str) -> Option<String> {
+    std::fs::read_to_string(path(name)?).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write(name: &str, content: &str) -> Result<(), Box<dyn Error>> {
+    let path = path(name).ok_or("The app data directory couldn't be found")?;
+    std::fs::create_dir_all(path.parent().unwrap())?;
+
+    // Write into a temporary file first, so a crash while writing doesn't corrupt the previous content
+    let temporary = path.with_extension("tmp");
+    std::fs::write(&temporary, content)?;
+    std::fs::rename(temporary, path)?;
+
+    Ok(())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn remove(name: &str) {
+    if let Some(path) = path(name) {
+        _ = std::fs::remove_file(path);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn read(name: &str) -> Option<String> {
+    crate::web::get_item(name)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn write(name: &str, content: &str) -> Result<(), Box<dyn Error>> {
+    if !crate::web::set_item(name, content) {
+        return Err("The browser storage is full".into());
+    }
+
+    Ok(())
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn remove(name: &str) {
+    crate::web::remove_item(name);
+}
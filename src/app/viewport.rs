@@ -1,8 +1,11 @@
 use super::context::EventContext;
 use super::Tree;
 use crate::elements::actionbar::Actionbar;
+use crate::elements::menu::Menu;
 use crate::elements::node::Element;
+use crate::elements::problems::Problems;
 use crate::elements::sidebar::Sidebar;
+use crate::elements::toast::Toast;
 use crate::elements::tooltip::Tooltip;
 use crate::elements::{toolbox::Toolbox, workspace::Workspace};
 use taffy::AlignContent::SpaceBetween;
@@ -29,10 +32,18 @@ impl Viewport {
         let toolbox = Toolbox::setup(tree, ctx);
         let tooltip = Tooltip::setup(tree, ctx);
         let sidebar = Sidebar::setup(tree, ctx);
+        let problems = Problems::setup(tree, ctx);
+        let toast = Toast::setup(tree, ctx);
+        let menu = Menu::setup(tree, ctx);
 
         tree.set_style(node, Self::STYLE).unwrap();
-        tree.set_children(node, &[workspace, actionbar, toolbox, sidebar, tooltip])
-            .unwrap();
+        tree.set_children(
+            node,
+            &[
+                workspace, actionbar, toolbox, sidebar, problems, toast, menu, tooltip,
+            ],
+        )
+        .unwrap();
 
         node
     }
@@ -0,0 +1,365 @@
+//! Headless command line interface. Runs without opening a window or an event loop.
+
+use crate::app::renderer::{ImageOptions, PngRenderer};
+use crate::data::codegen::{self, Language};
+use crate::data::diagram::Diagram;
+use crate::data::validation::{self, Severity};
+use crate::data::{file, import, Project};
+use crate::elements::workspace::Workspace;
+use crate::presentation::Colors;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::path::Path;
+use std::{cell::RefCell, rc::Rc};
+use vello::util::RenderContext;
+
+const USAGE: &str = "\
+Usage: UML [command]
+
+Without a command, the editor window is opened.
+
+Commands:
+  convert <input> <output>        Converts a project (to .bin, .json, .puml, .mmd or .svg)
+  export <input> --lang <lang>    Generates the source code into a zip archive
+      [--out <file>]              (csharp, java or typescript), unless the diagram has errors
+  render <input> --out <file>     Renders the whole diagram into a PNG image
+      [--scale <n>] [--padding <px>] [--transparent] [--dark]
+  validate <input>                Checks that the project is consistent and lists its problems
+  help                            Shows this message
+
+The input can be a project (.bin, .json), PlantUML (.puml), or C# sources (.cs, .zip, directory).";
+
+const COMMANDS: [&str; 5] = ["convert", "export", "render", "validate", "help"];
+
+/// Options without a value
+const FLAGS: [&str; 2] = ["transparent", "dark"];
+
+enum Command<'a> {
+    Convert {
+        input: &'a str,
+        output: &'a str,
+    },
+    Export {
+        input: &'a str,
+        language: Language,
+        output: Option<&'a str>,
+    },
+    Render {
+        input: &'a str,
+        output: &'a str,
+        options: ImageOptions,
+        dark: bool,
+    },
+    Validate {
+        input: &'a str,
+    },
+    Help,
+}
+
+/// Positional arguments and `--name value` options of a single command
+struct Arguments<'a> {
+    positional: Vec<&'a str>,
+    options: HashMap<&'a str, &'a str>,
+    flags: HashSet<&'a str>,
+}
+
+impl<'a> Arguments<'a> {
+    fn parse(args: &'a [String]) -> Result<Self, String> {
+        let mut arguments = Self {
+            positional: Vec::new(),
+            options: HashMap::new(),
+            flags: HashSet::new(),
+        };
+
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.strip_prefix("--") {
+                Some(flag) if FLAGS.contains(&flag) => {
+                    arguments.flags.insert(flag);
+                }
+                Some(name) => {
+                    let value = args.next().ok_or(format!("Missing value for --{name}"))?;
+                    arguments.options.insert(name, value);
+                }
+                None => arguments.positional.push(arg),
+            }
+        }
+
+        Ok(arguments)
+    }
+
+    /// Fails if there are any other options or a different number of positional arguments
+    fn expect(&self, positional: usize, options: &[&str], flags: &[&str]) -> Result<(), String> {
+        if self.positional.len() != positional {
+            return Err(format!(
+                "Expected {positional} argument(s), got {}",
+                self.positional.len()
+            ));
+        }
+
+        if let Some(name) = self.options.keys().find(|name| !options.contains(name)) {
+            return Err(format!("Unknown option --{name}"));
+        }
+
+        if let Some(flag) = self.flags.iter().find(|flag| !flags.contains(flag)) {
+            return Err(format!("Unknown option --{flag}"));
+        }
+
+        Ok(())
+    }
+
+    fn number(&self, name: &str) -> Result<Option<f64>, String> {
+        self.options
+            .get(name)
+            .map(|value| match value.parse::<f64>() {
+                Ok(number) if number.is_finite() && number >= 0. => Ok(number),
+                _ => Err(format!("Invalid number for --{name}: {value}")),
+            })
+            .transpose()
+    }
+}
+
+impl<'a> Command<'a> {
+    fn parse(command: &str, args: &'a Arguments<'a>) -> Result<Self, String> {
+        match command {
+            "convert" => {
+                args.expect(2, &[], &[])?;
+
+                Ok(Command::Convert {
+                    input: args.positional[0],
+                    output: args.positional[1],
+                })
+            }
+            "export" => {
+                args.expect(1, &["lang", "out"], &[])?;
+
+                let language = args.options.get("lang").ok_or("Missing --lang")?;
+                let language =
+                    Language::from_name(language).ok_or(format!("Unknown language: {language}"))?;
+
+                Ok(Command::Export {
+                    input: args.positional[0],
+                    language,
+                    output: args.options.get("out").copied(),
+                })
+            }
+            "render" => {
+                args.expect(1, &["out", "scale", "padding"], &FLAGS)?;
+
+                let mut options = ImageOptions {
+                    transparent: args.flags.contains("transparent"),
+                    ..Default::default()
+                };
+
+                if let Some(scale) = args.number("scale")? {
+                    if scale == 0. {
+                        return Err("The scale has to be positive".to_string());
+                    }
+
+                    options.scale = scale;
+                }
+
+                if let Some(padding) = args.number("padding")? {
+                    options.padding = padding;
+                }
+
+                Ok(Command::Render {
+                    input: args.positional[0],
+                    output: args.options.get("out").ok_or("Missing --out")?,
+                    options,
+                    dark: args.flags.contains("dark"),
+                })
+            }
+            "validate" => {
+                args.expect(1, &[], &[])?;
+
+                Ok(Command::Validate {
+                    input: args.positional[0],
+                })
+            }
+            _ => Ok(Command::Help),
+        }
+    }
+
+    async fn execute(self) -> Result<(), Box<dyn Error>> {
+        match self {
+            Command::Convert { input, output } => {
+                let project = load(input)?;
+                let extension = Path::new(output)
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .unwrap_or_default();
+
+                let data = match file::Format::from_extension(extension) {
+                    Some(format) => format.serialize(&project),
+                    None => Diagram::from_extension(extension)
+                        .ok_or(format!("Unsupported output format: {output}"))?
+                        .serialize(&project)
+                        .into_bytes(),
+                };
+
+                std::fs::write(output, data)?;
+            }
+            Command::Export {
+                input,
+                language,
+                output,
+            } => {
+                let project = load(input)?;
+                check(input, &project)?;
+
+                let files = language.generator().generate(&project);
+
+                let output = output
+                    .map(str::to_string)
+                    .unwrap_or_else(|| project.get_sanitized_name() + ".zip");
+
+                codegen::write_zip(&files, std::fs::File::create(output)?)?;
+            }
+            Command::Render {
+                input,
+                output,
+                options,
+                dark,
+            } => {
+                let mut project = load(input)?;
+                Workspace::layout(&mut project);
+
+                let context = Rc::new(RefCell::new(RenderContext::new()));
+                let mut renderer = PngRenderer::new(context).await;
+
+                let colors = if dark { &Colors::DARK } else { &Colors::LIGHT };
+                let image = renderer.render_diagram(&project, options, colors)?;
+
+                std::fs::write(output, image)?;
+            }
+            Command::Validate { input } => {
+                let project = load(input)?;
+                let errors = project.integrity_errors();
+
+                for error in errors.iter() {
+                    eprintln!("{input}: {error}");
+                }
+
+                if !errors.is_empty() {
+                    return Err(format!("{} problem(s) found", errors.len()).into());
+                }
+
+                check(input, &project)?;
+
+                println!(
+                    "{input}: {} entities, {} connections",
+                    project.entities.len(),
+                    project.connections.len()
+                );
+            }
+            Command::Help => println!("{USAGE}"),
+        }
+
+        Ok(())
+    }
+}
+
+/// Prints the problems found by the validation, fails if any of them is an error
+fn check(input: &str, project: &Project) -> Result<(), Box<dyn Error>> {
+    let diagnostics = validation::validate(project);
+
+    for diagnostic in diagnostics.iter() {
+        let entity = diagnostic
+            .target
+            .entity(project)
+            .and_then(|key| project.entities.get(key))
+            .map(|entity| entity.name.as_str())
+            .unwrap_or_default();
+
+        eprintln!(
+            "{input}: {}: {entity}: {}",
+            diagnostic.severity, diagnostic.message
+        );
+    }
+
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .count();
+
+    if errors > 0 {
+        return Err(format!("{} found", Severity::Error.count(errors)).into());
+    }
+
+    Ok(())
+}
+
+/// Loads a project file, source file or a directory of sources
+fn load(path: &str) -> Result<Project, Box<dyn Error>> {
+    let path = Path::new(path);
+
+    if path.is_dir() {
+        return import::from_directory(path);
+    }
+
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+
+    import::from_bytes(&std::fs::read(path)?, name)
+}
+
+/// Runs the command given in the arguments (without the program name).
+///
+/// Returns the exit code, or `None` if there's no command and the window should be opened instead.
+pub async fn run(args: &[String]) -> Option<i32> {
+    let (command, args) = args.split_first()?;
+    let command = command.trim_start_matches('-');
+
+    if !COMMANDS.contains(&command) {
+        return None;
+    }
+
+    #[cfg(windows)]
+    attach_console();
+
+    let arguments = match Arguments::parse(args) {
+        Ok(arguments) => arguments,
+        Err(error) => return Some(usage_error(&error)),
+    };
+
+    let command = match Command::parse(command, &arguments) {
+        Ok(command) => command,
+        Err(error) => return Some(usage_error(&error)),
+    };
+
+    match command.execute().await {
+        Ok(()) => Some(0),
+        Err(error) => {
+            eprintln!("error: {error}");
+            Some(1)
+        }
+    }
+}
+
+/// Attaches the console of the terminal the command was run from.
+///
+/// The app uses the windows subsystem, so it doesn't get a console on its own
+/// and everything printed would be lost.
+#[cfg(windows)]
+fn attach_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+
+    // Fails when it isn't run from a terminal, there's nowhere to print to then anyway
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+fn usage_error(error: &str) -> i32 {
+    eprintln!("error: {error}\n\n{USAGE}");
+    2
+}
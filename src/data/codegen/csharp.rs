@@ -0,0 +1,52 @@
+use super::{File, Generator};
+use crate::data::project::TokenVec;
+use crate::data::Project;
+use ogrim::xml;
+
+/// Generates a .NET project with a solution file
+pub struct CSharp;
+
+impl Generator for CSharp {
+    fn generate(&self, project: &Project) -> Vec<File> {
+        let name = project.get_sanitized_name();
+
+        // Project file
+        let proj = xml!(
+            <?xml version="1.0"?>
+            <Project Sdk="Microsoft.NET.Sdk">
+              <PropertyGroup>
+                <TargetFramework>"net8.0"</>
+                <ImplicitUsings>"enable"</>
+                <Nullable>"enable"</>
+              </>
+            </>
+        );
+
+        let project_file = format!("{name}/{name}.csproj");
+
+        // Solution file
+        let sln = xml!(
+            <?xml version="1.0"?>
+            <Solution>
+              <Project Path={project_file} />
+            </>
+        );
+
+        let mut files = vec![
+            File::new(project_file.clone(), proj.as_str().to_string() + "\n"),
+            File::new(format!("{name}.slnx"), sln.as_str().to_string() + "\n"),
+        ];
+
+        // Entities
+        for (key, entity) in &project.entities {
+            let tokens = project.entity_to_token(key);
+
+            files.push(File::new(
+                format!("{name}/{}.cs", project.sanitize(&entity.name)),
+                format!("{}\n", TokenVec(tokens)),
+            ));
+        }
+
+        files
+    }
+}
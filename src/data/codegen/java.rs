@@ -0,0 +1,472 @@
+use super::{
+    base_arguments, capitalize, decapitalize, generics, split_generic, BaseArgument, File,
+    Generator,
+};
+use crate::data::entity::{
+    AccessModifier, Direction, Entity, EntityType, Inheritance, Method, Mutability, Parameter,
+};
+use crate::data::project::{ConnectionKey, Project};
+use ogrim::xml;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+/// Generates a Maven project with one source file per entity
+pub struct Java;
+
+const INDENT: &str = "    ";
+
+impl Java {
+    /// Package name derived from the project name (e.g. "My Project" -> "myproject")
+    fn package(project: &Project) -> String {
+        let package = project
+            .name
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+            .collect::<String>()
+            .to_lowercase();
+
+        match package.chars().next() {
+            None => "project".to_string(),
+            Some(c) if c.is_ascii_digit() => format!("_{package}"),
+            _ => package,
+        }
+    }
+
+    fn modifier(modifier: AccessModifier) -> &'static str {
+        match modifier {
+            AccessModifier::Public => "public",
+            AccessModifier::Protected => "protected",
+            AccessModifier::Private => "private",
+        }
+    }
+
+    /// Converts a C# type into its Java equivalent, collecting the required imports
+    fn convert_type(r#type: &str, boxed: bool, imports: &mut BTreeSet<&'static str>) -> String {
+        let r#type = r#type.trim();
+
+        if let Some(inner) = r#type.strip_suffix('?') {
+            return Self::convert_type(inner, true, imports);
+        }
+
+        if let Some(inner) = r#type.strip_suffix("[]") {
+            return Self::convert_type(inner, false, imports) + "[]";
+        }
+
+        if let Some((base, arguments)) = split_generic(r#type) {
+            let base = match base {
+                "List" | "IList" | "ICollection" | "IEnumerable" | "IReadOnlyList" => {
+                    imports.insert("java.util.List");
+                    "List"
+                }
+                "Dictionary" | "IDictionary" | "IReadOnlyDictionary" => {
+                    imports.insert("java.util.Map");
+                    "Map"
+                }
+                "HashSet" | "ISet" => {
+                    imports.insert("java.util.Set");
+                    "Set"
+                }
+                base => base,
+            };
+
+            let arguments = arguments
+                .into_iter()
+                .map(|argument| Self::convert_type(argument, true, imports))
+                .collect::<Vec<_>>();
+
+            return format!("{base}<{}>", arguments.join(", "));
+        }
+
+        let (primitive, wrapper) = match r#type {
+            "" | "object" => ("Object", "Object"),
+            "string" => ("String", "String"),
+            "bool" => ("boolean", "Boolean"),
+            "byte" | "sbyte" => ("byte", "Byte"),
+            "short" | "ushort" => ("short", "Short"),
+            "int" | "uint" => ("int", "Integer"),
+            "long" | "ulong" => ("long", "Long"),
+            "float" => ("float", "Float"),
+            "double" | "decimal" => ("double", "Double"),
+            "char" => ("char", "Character"),
+            "void" => ("void", "Void"),
+            other => return other.to_string(),
+        };
+
+        if boxed { wrapper } else { primitive }.to_string()
+    }
+
+    /// The parameter list of a method. Java doesn't have default values or passing by reference,
+    /// so only the variable arguments are kept.
+    fn parameters(parameters: &[Parameter], imports: &mut BTreeSet<&'static str>) -> String {
+        let parameters = parameters.iter().enumerate().map(|(i, parameter)| {
+            let r#type = Self::convert_type(&parameter.r#type, false, imports);
+            let name = parameter.name_or_index(i);
+
+            match parameter.direction {
+                Direction::Params => {
+                    let element = r#type.strip_suffix("[]").unwrap_or(&r#type);
+                    format!("{element}... {name}")
+                }
+                _ => format!("{type} {name}"),
+            }
+        });
+
+        parameters.collect::<Vec<_>>().join(", ")
+    }
+
+    /// The value final fields are initialized with
+    fn default_value(r#type: &str) -> &'static str {
+        match r#type {
+            "boolean" => "false",
+            "byte" | "short" | "int" | "long" | "float" | "double" => "0",
+            "char" => "'\\0'",
+            _ => "null",
+        }
+    }
+
+    /// The call of the parent's constructor, unless the implicit `super()` is enough
+    fn super_call(
+        project: &Project,
+        entity: &Entity,
+        constructor: &Method,
+        imports: &mut BTreeSet<&'static str>,
+    ) -> String {
+        let arguments = base_arguments(project, entity, constructor).unwrap_or_default();
+        if arguments.is_empty() {
+            return String::new();
+        }
+
+        let arguments = arguments.iter().map(|argument| match *argument {
+            BaseArgument::Forward(parameter, i) => parameter.name_or_index(i),
+            BaseArgument::Missing(parameter, i) => {
+                let r#type = Self::convert_type(&parameter.r#type, false, imports);
+                let value = Self::default_value(&r#type);
+
+                format!("{value} /* TODO: {} */", parameter.name_or_index(i))
+            }
+        });
+
+        let arguments = arguments.collect::<Vec<_>>().join(", ");
+        format!("{INDENT}{INDENT}super({arguments});\n")
+    }
+
+    /// Enums with explicit values store them in a field, because Java enums aren't numbers
+    fn enumeration(project: &Project, package: &str, entity: &Entity) -> String {
+        let name = project.sanitize(&entity.name);
+
+        let mut next = 0;
+        let literals = entity
+            .literals
+            .iter()
+            .filter(|literal| !literal.name.trim().is_empty())
+            .map(|literal| {
+                // Numbered like in C#, continuing from the previous value
+                let value = literal.value.unwrap_or(next);
+                next = value + 1;
+
+                (literal.name.trim(), value)
+            })
+            .collect::<Vec<_>>();
+
+        let mut output = format!("package {package};\n\npublic enum {name} {{");
+
+        if entity
+            .literals
+            .iter()
+            .all(|literal| literal.value.is_none())
+        {
+            let names = literals.iter().map(|(name, _)| format!("\n{INDENT}{name}"));
+            output += &names.collect::<Vec<_>>().join(",");
+            output += "\n}\n";
+
+            return output;
+        }
+
+        let r#type = if literals.iter().all(|(_, v)| i32::try_from(*v).is_ok()) {
+            "int"
+        } else {
+            "long"
+        };
+
+        let values = literals
+            .iter()
+            .map(|(name, value)| format!("\n{INDENT}{name}({value})"));
+        output += &values.collect::<Vec<_>>().join(",");
+
+        _ = write!(
+            output,
+            ";\n\n{INDENT}private final {type} value;\n\n{INDENT}{name}({type} value) {{\n{INDENT}{INDENT}this.value = value;\n{INDENT}}}"
+        );
+        _ = write!(
+            output,
+            "\n\n{INDENT}public {type} getValue() {{\n{INDENT}{INDENT}return value;\n{INDENT}}}\n}}\n"
+        );
+
+        output
+    }
+
+    fn entity(project: &Project, package: &str, entity: &Entity) -> String {
+        let interface = entity.entity_type == EntityType::Interface;
+
+        let mut imports = BTreeSet::new();
+        let mut body = String::new();
+
+        // Fields (interfaces can't have them, so only the accessors are declared)
+        for field in &entity.fields {
+            if field.name.trim().is_empty() {
+                continue;
+            }
+
+            let r#type = Self::convert_type(&field.r#type, false, &mut imports);
+            let name = decapitalize(field.name.trim());
+            let accessor = capitalize(field.name.trim());
+            let getter = if r#type == "boolean" { "is" } else { "get" };
+            let value = Self::default_value(&r#type);
+
+            // Constants don't have any accessors (and they're implicitly static in interfaces)
+            if field.mutability == Mutability::Const {
+                let modifier = match interface {
+                    true => String::new(),
+                    false => Self::modifier(field.modifier).to_string() + " static final ",
+                };
+
+                _ = writeln!(body, "\n{INDENT}{modifier}{type} {name} = {value};");
+                continue;
+            }
+
+            let setter = field.mutability == Mutability::Mutable;
+
+            // Plain fields are declared as they are (interfaces only get the accessors)
+            if !field.is_property() && !interface {
+                let modifier = Self::modifier(field.modifier);
+                let r#static = if field.is_static { " static" } else { "" };
+
+                if setter {
+                    _ = writeln!(body, "\n{INDENT}{modifier}{static} {type} {name};");
+                } else {
+                    _ = writeln!(
+                        body,
+                        "\n{INDENT}{modifier}{static} final {type} {name} = {value};"
+                    );
+                }
+
+                continue;
+            }
+
+            if interface {
+                _ = writeln!(body, "\n{INDENT}{type} {getter}{accessor}();");
+
+                if setter {
+                    _ = writeln!(body, "\n{INDENT}void set{accessor}({type} {name});");
+                }
+
+                continue;
+            }
+
+            let modifier = Self::modifier(field.modifier);
+            let (r#static, owner) = match field.is_static {
+                true => (" static", project.sanitize(&entity.name)),
+                false => ("", "this".to_string()),
+            };
+
+            // Final fields have to be initialized
+            if setter {
+                _ = writeln!(body, "\n{INDENT}private{static} {type} {name};");
+            } else {
+                _ = writeln!(
+                    body,
+                    "\n{INDENT}private{static} final {type} {name} = {value};"
+                );
+            }
+
+            _ = writeln!(
+                body,
+                "\n{INDENT}{modifier}{static} {type} {getter}{accessor}() {{\n{INDENT}{INDENT}return {name};\n{INDENT}}}"
+            );
+
+            if setter {
+                _ = writeln!(
+                    body,
+                    "\n{INDENT}{modifier}{static} void set{accessor}({type} {name}) {{\n{INDENT}{INDENT}{owner}.{name} = {name};\n{INDENT}}}"
+                );
+            }
+        }
+
+        // Methods
+        for method in &entity.methods {
+            if method.name.trim().is_empty() {
+                continue;
+            }
+
+            let arguments = Self::parameters(&method.arguments, &mut imports);
+
+            // Interfaces can't have constructors
+            if method.is_constructor() {
+                if !interface {
+                    let modifier = Self::modifier(method.modifier);
+                    let name = project.sanitize(&entity.name);
+                    let base = Self::super_call(project, entity, method, &mut imports);
+
+                    _ = writeln!(
+                        body,
+                        "\n{INDENT}{modifier} {name}({arguments}) {{\n{base}{INDENT}{INDENT}throw new UnsupportedOperationException(\"Not implemented\");\n{INDENT}}}"
+                    );
+                }
+
+                continue;
+            }
+
+            let return_type = match method.return_type.trim() {
+                "" => "void".to_string(),
+                r#type => Self::convert_type(r#type, false, &mut imports),
+            };
+
+            let name = decapitalize(method.name.trim());
+            let generics = match method.generics.is_empty() {
+                true => String::new(),
+                false => {
+                    generics(&method.generics, |c| {
+                        Self::convert_type(c, true, &mut imports)
+                    }) + " "
+                }
+            };
+
+            // Methods are virtual by default in Java
+            let (keywords, annotation) = match (method.is_static, method.inheritance) {
+                (true, _) => ("static ", ""),
+                (_, Inheritance::Abstract) => ("abstract ", ""),
+                (_, Inheritance::Override) => ("", "\n    @Override"),
+                _ => ("", ""),
+            };
+
+            let declaration = if interface {
+                format!("{generics}{return_type} {name}({arguments})")
+            } else {
+                let modifier = Self::modifier(method.modifier);
+                format!("{modifier} {keywords}{generics}{return_type} {name}({arguments})")
+            };
+
+            if (interface && !method.is_static) || method.inheritance == Inheritance::Abstract {
+                _ = writeln!(body, "{annotation}\n{INDENT}{declaration};");
+            } else if interface {
+                _ = writeln!(
+                    body,
+                    "\n{INDENT}static {declaration} {{\n{INDENT}{INDENT}throw new UnsupportedOperationException(\"Not implemented\");\n{INDENT}}}"
+                );
+            } else {
+                _ = writeln!(
+                    body,
+                    "{annotation}\n{INDENT}{declaration} {{\n{INDENT}{INDENT}throw new UnsupportedOperationException(\"Not implemented\");\n{INDENT}}}"
+                );
+            }
+        }
+
+        // Declaration
+        let generics = generics(&entity.generics, |c| {
+            Self::convert_type(c, true, &mut imports)
+        });
+
+        let mut reference = |connection: ConnectionKey| {
+            let connection = &project.connections[connection];
+            let name = project.sanitize(&project.entities[connection.to.entity].name);
+
+            let arguments = connection
+                .arguments
+                .iter()
+                .map(|argument| Self::convert_type(argument, true, &mut imports))
+                .collect::<Vec<_>>();
+
+            match arguments.is_empty() {
+                true => name,
+                false => format!("{name}<{}>", arguments.join(", ")),
+            }
+        };
+
+        let parent = entity.parent.map(&mut reference);
+        let interfaces = entity
+            .implements
+            .iter()
+            .map(|&connection| reference(connection))
+            .collect::<Vec<_>>();
+
+        let mut output = format!("package {package};\n\n");
+
+        for import in &imports {
+            _ = writeln!(output, "import {import};");
+        }
+
+        if !imports.is_empty() {
+            output.push('\n');
+        }
+
+        output += match entity.entity_type {
+            EntityType::Class => "public class ",
+            EntityType::AbstractClass => "public abstract class ",
+            EntityType::SealedClass => "public final class ",
+            EntityType::Interface => "public interface ",
+            EntityType::Enum => unreachable!("Enums are generated separately"),
+        };
+
+        output += &project.sanitize(&entity.name);
+        output += &generics;
+
+        if let Some(parent) = parent {
+            _ = write!(output, " extends {parent}");
+        }
+
+        if !interfaces.is_empty() {
+            let keyword = if interface { "extends" } else { "implements" };
+            _ = write!(output, " {keyword} {}", interfaces.join(", "));
+        }
+
+        output += " {";
+        output += &body;
+        output += "}\n";
+
+        output
+    }
+}
+
+impl Generator for Java {
+    fn generate(&self, project: &Project) -> Vec<File> {
+        let name = project.get_sanitized_name();
+        let package = Self::package(project);
+        let artifact = name.to_lowercase().replace('_', "-");
+
+        let pom = xml!(
+            <?xml version="1.0"?>
+            <project xmlns="http://maven.apache.org/POM/4.0.0">
+              <modelVersion>"4.0.0"</>
+              <groupId>{package}</>
+              <artifactId>{artifact}</>
+              <version>"1.0.0"</>
+              <properties>
+                <"maven.compiler.source">"17"</>
+                <"maven.compiler.target">"17"</>
+                <"project.build.sourceEncoding">"UTF-8"</>
+              </>
+            </>
+        );
+
+        let mut files = vec![File::new(
+            format!("{name}/pom.xml"),
+            pom.as_str().to_string() + "\n",
+        )];
+
+        for entity in project.entities.values() {
+            files.push(File::new(
+                format!(
+                    "{name}/src/main/java/{package}/{}.java",
+                    project.sanitize(&entity.name)
+                ),
+                if entity.entity_type == EntityType::Enum {
+                    Self::enumeration(project, &package, entity)
+                } else {
+                    Self::entity(project, &package, entity)
+                },
+            ));
+        }
+
+        files
+    }
+}
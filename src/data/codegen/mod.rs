@@ -0,0 +1,267 @@
+use super::connection::{Multiplicity, RelationType};
+use super::entity::{Method, Parameter, TypeParameter};
+use super::{reference, Entity, Project};
+use std::collections::HashSet;
+use std::fmt::{self, Display};
+use std::io::{Seek, Write};
+use zip::result::ZipResult;
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+pub mod csharp;
+pub mod java;
+pub mod typescript;
+
+/// A single generated source file
+pub struct File {
+    /// Path relative to the root of the exported archive
+    pub path: String,
+    pub content: String,
+}
+
+impl File {
+    pub fn new(path: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            content: content.into(),
+        }
+    }
+}
+
+pub trait Generator {
+    /// Generates the source files (including the build files) for the whole project
+    fn generate(&self, project: &Project) -> Vec<File>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Language {
+    CSharp,
+    Java,
+    TypeScript,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::CSharp, Language::Java, Language::TypeScript];
+
+    pub fn generator(self) -> Box<dyn Generator> {
+        match self {
+            Language::CSharp => Box::new(csharp::CSharp),
+            Language::Java => Box::new(java::Java),
+            Language::TypeScript => Box::new(typescript::TypeScript),
+        }
+    }
+
+    /// Finds a language by its name (e.g. "csharp", "c#" or "ts")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "csharp" | "c#" | "cs" => Some(Language::CSharp),
+            "java" => Some(Language::Java),
+            "typescript" | "ts" => Some(Language::TypeScript),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Language::CSharp => write!(f, "C#"),
+            Language::Java => write!(f, "Java"),
+            Language::TypeScript => write!(f, "TypeScript"),
+        }
+    }
+}
+
+/// Writes the generated files into a zip archive
+pub fn write_zip<W: Write + Seek>(files: &[File], writer: W) -> ZipResult<W> {
+    let mut zip = ZipWriter::new(writer);
+
+    let options: FileOptions<()> = FileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .unix_permissions(0o755);
+
+    for file in files {
+        zip.start_file(file.path.as_str(), options)?;
+        zip.write_all(file.content.as_bytes())?;
+    }
+
+    zip.finish()
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|c| c.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+fn decapitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|c| c.to_lowercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Splits a generic type into its base and top-level arguments (e.g. "Dictionary<string, List<int>>")
+fn split_generic(r#type: &str) -> Option<(&str, Vec<&str>)> {
+    let start = r#type.find('<')?;
+    let inner = r#type.strip_suffix('>')?.get(start + 1..)?;
+
+    let mut arguments = Vec::new();
+    let mut depth = 0;
+    let mut last = 0;
+
+    for (i, c) in inner.char_indices() {
+        match c {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                arguments.push(inner[last..i].trim());
+                last = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    arguments.push(inner[last..].trim());
+
+    Some((r#type[..start].trim(), arguments))
+}
+
+/// Type parameters in the syntax of Java and TypeScript (e.g. `<T extends IEntity & IComparable>`).
+///
+/// C# constraints which aren't types (e.g. `class` or `new()`) can't be expressed, so they're left out.
+fn generics(parameters: &[TypeParameter], mut convert: impl FnMut(&str) -> String) -> String {
+    if parameters.is_empty() {
+        return String::new();
+    }
+
+    let parameters = parameters
+        .iter()
+        .map(|parameter| {
+            let constraints = parameter
+                .constraints
+                .iter()
+                .map(|c| c.trim())
+                .filter(|c| !matches!(*c, "class" | "struct" | "new()" | "notnull" | "unmanaged"))
+                .map(&mut convert)
+                .collect::<Vec<_>>();
+
+            match constraints.is_empty() {
+                true => parameter.name.clone(),
+                false => format!("{} extends {}", parameter.name, constraints.join(" & ")),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    format!("<{}>", parameters.join(", "))
+}
+
+/// Entities the given entity can navigate to through its associations (with the multiplicity of the other end).
+///
+/// Two-way associations work in both directions, one-way associations only from the source,
+/// and aggregations / compositions from the whole (the end with the diamond) to its parts.
+fn associations<'a>(project: &'a Project, entity: &Entity) -> Vec<(&'a Entity, &'a Multiplicity)> {
+    let mut associations = Vec::new();
+
+    for connection in entity
+        .connections
+        .iter()
+        .filter_map(|&key| project.connections.get(key))
+    {
+        let (from, to) = (&connection.from, &connection.to);
+
+        let other = match connection.relation {
+            RelationType::Association if from.entity == entity.key => to,
+            RelationType::Association => from,
+            RelationType::OneWayAssociation if from.entity == entity.key => to,
+            RelationType::Aggregation | RelationType::Composition if to.entity == entity.key => {
+                from
+            }
+            _ => continue,
+        };
+
+        if let Some(other_entity) = project.entities.get(other.entity) {
+            associations.push((other_entity, &other.multiplicity));
+        }
+    }
+
+    associations
+}
+
+/// An argument passed to the parent's constructor (with the index of the parameter, for the generated names)
+pub enum BaseArgument<'a> {
+    /// One of the constructor's own parameters
+    Forward(&'a Parameter, usize),
+
+    /// A parameter of the parent's constructor, which doesn't have a matching one and needs a placeholder
+    Missing(&'a Parameter, usize),
+}
+
+/// The arguments of the parent's constructor called by a constructor of the entity (`None` without a parent).
+///
+/// The parameters are matched by the type (preferably with the same name). The parent's constructor with
+/// the most parameters, which can all be forwarded, is called. If there isn't one, the one with the fewest
+/// missing parameters is called with placeholders. Parents without any constructors have an implicit one.
+pub fn base_arguments<'a>(
+    project: &'a Project,
+    entity: &Entity,
+    constructor: &'a Method,
+) -> Option<Vec<BaseArgument<'a>>> {
+    let connection = &project.connections[entity.parent?];
+    let parent = &project.entities[connection.to.entity];
+
+    // The parent's parameters use its own type parameters (e.g. `T` in `Repository<User>`)
+    let resolve = |r#type: &str| {
+        let arguments = parent.generics.iter().zip(&connection.arguments);
+        arguments.fold(r#type.trim().to_string(), |r#type, (parameter, argument)| {
+            reference::replace(&r#type, &parameter.name, argument.trim())
+        })
+    };
+
+    let call = |base: &'a Method| {
+        let mut used = HashSet::new();
+
+        let arguments = base.arguments.iter().enumerate().map(|(i, parameter)| {
+            let r#type = resolve(&parameter.r#type);
+            let candidates = constructor
+                .arguments
+                .iter()
+                .enumerate()
+                .filter(|(j, p)| !used.contains(j) && p.direction == parameter.direction)
+                .filter(|(_, p)| p.r#type.trim() == r#type)
+                .collect::<Vec<_>>();
+
+            let found = candidates
+                .iter()
+                .find(|(_, p)| p.name.trim() == parameter.name.trim())
+                .or(candidates.first());
+
+            match found {
+                Some(&(j, p)) => {
+                    used.insert(j);
+                    BaseArgument::Forward(p, j)
+                }
+                None => BaseArgument::Missing(parameter, i),
+            }
+        });
+
+        arguments.collect::<Vec<_>>()
+    };
+
+    // The longest call without placeholders, otherwise the one with the fewest placeholders
+    let calls = parent.methods.iter().filter(|m| m.is_constructor()).map(call);
+    let best = calls.max_by_key(|arguments| {
+        let missing = arguments.iter();
+        let missing = missing.filter(|a| matches!(a, BaseArgument::Missing(..)));
+
+        match missing.count() {
+            0 => (true, arguments.len() as isize),
+            missing => (false, -(missing as isize)),
+        }
+    });
+
+    Some(best.unwrap_or_default())
+}
@@ -0,0 +1,460 @@
+use super::{
+    associations, base_arguments, decapitalize, generics, split_generic, BaseArgument, File,
+    Generator,
+};
+use crate::data::connection::Multiplicity;
+use crate::data::entity::{
+    AccessModifier, Direction, Entity, EntityType, Inheritance, Method, Mutability, Parameter,
+};
+use crate::data::project::{ConnectionKey, Project};
+use serde_json::json;
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Write;
+
+/// Generates an npm package with one module per entity
+pub struct TypeScript;
+
+const INDENT: &str = "    ";
+
+/// Converts the types of a single module, keeping track of the entities it references
+struct Module<'a> {
+    entities: &'a HashSet<String>,
+    imports: BTreeSet<String>,
+}
+
+impl Module<'_> {
+    /// Converts a C# type into its TypeScript equivalent
+    fn convert_type(&mut self, r#type: &str) -> String {
+        let r#type = r#type.trim();
+
+        if let Some(inner) = r#type.strip_suffix('?') {
+            return format!("{} | null", self.convert_type(inner));
+        }
+
+        if let Some(inner) = r#type.strip_suffix("[]") {
+            return Self::array(self.convert_type(inner));
+        }
+
+        if let Some((base, arguments)) = split_generic(r#type) {
+            let mut arguments = arguments
+                .into_iter()
+                .map(|argument| self.convert_type(argument))
+                .collect::<Vec<_>>();
+
+            return match base {
+                "List" | "IList" | "ICollection" | "IEnumerable" | "IReadOnlyList"
+                    if arguments.len() == 1 =>
+                {
+                    Self::array(arguments.remove(0))
+                }
+                "Dictionary" | "IDictionary" | "IReadOnlyDictionary" => {
+                    format!("Record<{}>", arguments.join(", "))
+                }
+                "HashSet" | "ISet" => format!("Set<{}>", arguments.join(", ")),
+                base => format!("{}<{}>", self.reference(base), arguments.join(", ")),
+            };
+        }
+
+        match r#type {
+            "" | "object" => "unknown".to_string(),
+            "string" | "char" | "Guid" => "string".to_string(),
+            "bool" => "boolean".to_string(),
+            "byte" | "sbyte" | "short" | "ushort" | "int" | "uint" | "long" | "ulong" | "float"
+            | "double" | "decimal" => "number".to_string(),
+            "DateTime" | "DateTimeOffset" => "Date".to_string(),
+            "void" => "void".to_string(),
+            other => self.reference(other),
+        }
+    }
+
+    fn array(r#type: String) -> String {
+        if r#type.contains(' ') {
+            format!("({type})[]")
+        } else {
+            r#type + "[]"
+        }
+    }
+
+    /// The parameter list of a method. Parameters with a default value are optional
+    /// in the declarations without a body.
+    fn parameters(&mut self, parameters: &[Parameter], defaults: bool) -> String {
+        let parameters = parameters.iter().enumerate().map(|(i, parameter)| {
+            let r#type = self.convert_type(&parameter.r#type);
+            let name = parameter.name_or_index(i);
+
+            match (parameter.direction, &parameter.default) {
+                (Direction::Params, _) => format!("...{name}: {type}"),
+                (_, Some(default)) if defaults => format!("{name}: {type} = {default}"),
+                (_, Some(_)) => format!("{name}?: {type}"),
+                (_, None) => format!("{name}: {type}"),
+            }
+        });
+
+        parameters.collect::<Vec<_>>().join(", ")
+    }
+
+    /// Adds an import if the type is one of the project's entities
+    fn reference(&mut self, name: &str) -> String {
+        if self.entities.contains(name) {
+            self.imports.insert(name.to_string());
+        }
+
+        name.to_string()
+    }
+}
+
+impl TypeScript {
+    fn modifier(modifier: AccessModifier) -> &'static str {
+        match modifier {
+            AccessModifier::Public => "public",
+            AccessModifier::Protected => "protected",
+            AccessModifier::Private => "private",
+        }
+    }
+
+    /// The call of the parent's constructor, which has to be the first statement of a derived class' constructor
+    fn super_call(project: &Project, entity: &Entity, constructor: &Method) -> String {
+        let Some(arguments) = base_arguments(project, entity, constructor) else {
+            return String::new();
+        };
+
+        let arguments = arguments.iter().map(|argument| match *argument {
+            BaseArgument::Forward(parameter, i) if parameter.direction == Direction::Params => {
+                format!("...{}", parameter.name_or_index(i))
+            }
+            BaseArgument::Forward(parameter, i) => parameter.name_or_index(i),
+            BaseArgument::Missing(parameter, i) => {
+                format!("undefined! /* TODO: {} */", parameter.name_or_index(i))
+            }
+        });
+
+        let arguments = arguments.collect::<Vec<_>>().join(", ");
+        format!("{INDENT}{INDENT}super({arguments});\n")
+    }
+
+    fn enumeration(project: &Project, entity: &Entity) -> String {
+        let name = project.sanitize(&entity.name);
+
+        let mut body = String::new();
+
+        for literal in &entity.literals {
+            let literal_name = literal.name.trim();
+            if literal_name.is_empty() {
+                continue;
+            }
+
+            match literal.value {
+                Some(value) => _ = writeln!(body, "{INDENT}{literal_name} = {value},"),
+                None => _ = writeln!(body, "{INDENT}{literal_name},"),
+            }
+        }
+
+        if body.is_empty() {
+            format!("export enum {name} {{}}\n")
+        } else {
+            format!("export enum {name} {{\n{body}}}\n")
+        }
+    }
+
+    fn entity(project: &Project, entities: &HashSet<String>, entity: &Entity) -> String {
+        let interface = entity.entity_type == EntityType::Interface;
+        let own_name = project.sanitize(&entity.name);
+
+        let mut module = Module {
+            entities,
+            imports: BTreeSet::new(),
+        };
+
+        let mut body = String::new();
+
+        // Fields
+        let mut properties = HashSet::new();
+
+        for field in &entity.fields {
+            if field.name.trim().is_empty() {
+                continue;
+            }
+
+            let is_static = field.is_static || field.mutability == Mutability::Const;
+
+            // Interfaces can't declare static members
+            if interface && is_static {
+                continue;
+            }
+
+            let name = decapitalize(field.name.trim());
+            let r#type = module.convert_type(&field.r#type);
+
+            let keywords = match (is_static, field.mutability) {
+                (true, Mutability::Mutable) => "static ",
+                (true, _) => "static readonly ",
+                (false, Mutability::Mutable) => "",
+                (false, _) => "readonly ",
+            };
+
+            if interface {
+                _ = writeln!(body, "{INDENT}{keywords}{name}: {type};");
+            } else {
+                let modifier = Self::modifier(field.modifier);
+                _ = writeln!(body, "{INDENT}{modifier} {keywords}{name}: {type};");
+            }
+
+            properties.insert(name);
+        }
+
+        // Associations
+        for (other, multiplicity) in associations(project, entity) {
+            let other = project.sanitize(&other.name);
+            let name = decapitalize(&other);
+
+            // Don't override a field with the same name
+            if !properties.insert(name.clone()) {
+                continue;
+            }
+
+            let r#type = match multiplicity {
+                Multiplicity::One => module.reference(&other),
+                Multiplicity::Many => module.reference(&other) + "[]",
+            };
+
+            if interface {
+                _ = writeln!(body, "{INDENT}{name}: {type};");
+            } else {
+                _ = writeln!(body, "{INDENT}public {name}: {type};");
+            }
+        }
+
+        // Constructors (overloads share a single implementation)
+        let constructors = entity
+            .methods
+            .iter()
+            .filter(|method| method.is_constructor() && !interface)
+            .collect::<Vec<_>>();
+
+        if !constructors.is_empty() && !body.is_empty() {
+            body.push('\n');
+        }
+
+        // Overloads have to share the modifier, so it's public unless they all agree
+        let modifier = match constructors.split_first() {
+            Some((first, rest)) if rest.iter().all(|c| c.modifier == first.modifier) => {
+                Self::modifier(first.modifier)
+            }
+            _ => "public",
+        };
+
+        for constructor in &constructors {
+            let arguments = module.parameters(&constructor.arguments, constructors.len() == 1);
+
+            if constructors.len() == 1 {
+                let base = Self::super_call(project, entity, constructor);
+
+                _ = writeln!(
+                    body,
+                    "{INDENT}{modifier} constructor({arguments}) {{\n{base}{INDENT}{INDENT}throw new Error(\"Not implemented\");\n{INDENT}}}"
+                );
+            } else {
+                _ = writeln!(body, "{INDENT}{modifier} constructor({arguments});");
+            }
+        }
+
+        if constructors.len() > 1 {
+            // The arguments of the shared implementation can't be forwarded
+            let base = Self::super_call(project, entity, &Method::default());
+
+            _ = writeln!(
+                body,
+                "{INDENT}{modifier} constructor(...args: unknown[]) {{\n{base}{INDENT}{INDENT}throw new Error(\"Not implemented\");\n{INDENT}}}"
+            );
+        }
+
+        // Methods
+        for method in &entity.methods {
+            if method.name.trim().is_empty()
+                || method.is_constructor()
+                || (interface && method.is_static)
+            {
+                continue;
+            }
+
+            let return_type = match method.return_type.trim() {
+                "" => "void".to_string(),
+                r#type => module.convert_type(r#type),
+            };
+
+            // Only the implementations can have default values
+            let implemented = !interface && method.inheritance != Inheritance::Abstract;
+            let arguments = module.parameters(&method.arguments, implemented || method.is_static);
+
+            let name = decapitalize(method.name.trim())
+                + &generics(&method.generics, |c| module.convert_type(c));
+
+            if !body.is_empty() {
+                body.push('\n');
+            }
+
+            let keywords = match (method.is_static, method.inheritance) {
+                (true, _) => " static",
+                (_, Inheritance::Abstract) => " abstract",
+                (_, Inheritance::Override) if entity.parent.is_some() => " override",
+                _ => "",
+            };
+
+            if interface {
+                _ = writeln!(body, "{INDENT}{name}({arguments}): {return_type};");
+            } else if method.inheritance == Inheritance::Abstract && !method.is_static {
+                let modifier = Self::modifier(method.modifier);
+                _ = writeln!(
+                    body,
+                    "{INDENT}{modifier}{keywords} {name}({arguments}): {return_type};"
+                );
+            } else {
+                let modifier = Self::modifier(method.modifier);
+
+                _ = writeln!(
+                    body,
+                    "{INDENT}{modifier}{keywords} {name}({arguments}): {return_type} {{\n{INDENT}{INDENT}throw new Error(\"Not implemented\");\n{INDENT}}}"
+                );
+            }
+        }
+
+        // Declaration
+        let generics = generics(&entity.generics, |c| module.convert_type(c));
+
+        let mut reference = |connection: ConnectionKey| {
+            let connection = &project.connections[connection];
+            let name =
+                module.reference(&project.sanitize(&project.entities[connection.to.entity].name));
+
+            let arguments = connection
+                .arguments
+                .iter()
+                .map(|argument| module.convert_type(argument))
+                .collect::<Vec<_>>();
+
+            match arguments.is_empty() {
+                true => name,
+                false => format!("{name}<{}>", arguments.join(", ")),
+            }
+        };
+
+        let parent = entity.parent.map(&mut reference);
+        let interfaces = entity
+            .implements
+            .iter()
+            .map(|&connection| reference(connection))
+            .collect::<Vec<_>>();
+
+        let mut declaration = match entity.entity_type {
+            EntityType::Class | EntityType::SealedClass => "export class ",
+            EntityType::AbstractClass => "export abstract class ",
+            EntityType::Interface => "export interface ",
+            EntityType::Enum => unreachable!("Enums are generated separately"),
+        }
+        .to_string();
+
+        declaration += &own_name;
+        declaration += &generics;
+
+        if let Some(parent) = parent {
+            _ = write!(declaration, " extends {parent}");
+        }
+
+        if !interfaces.is_empty() {
+            let keyword = if interface { "extends" } else { "implements" };
+            _ = write!(declaration, " {keyword} {}", interfaces.join(", "));
+        }
+
+        // Imports
+        module.imports.remove(&own_name);
+
+        let mut output = String::new();
+
+        for import in &module.imports {
+            _ = writeln!(output, "import {{ {import} }} from \"./{import}\";");
+        }
+
+        if !module.imports.is_empty() {
+            output.push('\n');
+        }
+
+        if body.is_empty() {
+            _ = writeln!(output, "{declaration} {{}}");
+        } else {
+            _ = writeln!(output, "{declaration} {{\n{body}}}");
+        }
+
+        output
+    }
+}
+
+impl Generator for TypeScript {
+    fn generate(&self, project: &Project) -> Vec<File> {
+        let name = project.get_sanitized_name();
+        let package = name.to_lowercase().replace('_', "-");
+
+        let package_json = json!({
+            "name": package,
+            "version": "1.0.0",
+            "private": true,
+            "main": "dist/index.js",
+            "types": "dist/index.d.ts",
+            "scripts": {
+                "build": "tsc"
+            },
+            "devDependencies": {
+                "typescript": "^5.0.0"
+            }
+        });
+
+        let tsconfig = json!({
+            "compilerOptions": {
+                "target": "ES2020",
+                "module": "commonjs",
+                "declaration": true,
+                "strict": true,
+                "strictPropertyInitialization": false,
+                "outDir": "dist",
+                "rootDir": "src"
+            },
+            "include": ["src"]
+        });
+
+        let mut files = vec![
+            File::new(
+                format!("{name}/package.json"),
+                serde_json::to_string_pretty(&package_json).unwrap() + "\n",
+            ),
+            File::new(
+                format!("{name}/tsconfig.json"),
+                serde_json::to_string_pretty(&tsconfig).unwrap() + "\n",
+            ),
+        ];
+
+        let entities = project
+            .entities
+            .values()
+            .map(|entity| project.sanitize(&entity.name))
+            .collect::<HashSet<_>>();
+
+        let mut index = String::new();
+
+        for entity in project.entities.values() {
+            let module = project.sanitize(&entity.name);
+            _ = writeln!(index, "export * from \"./{module}\";");
+
+            files.push(File::new(
+                format!("{name}/src/{module}.ts"),
+                if entity.entity_type == EntityType::Enum {
+                    Self::enumeration(project, entity)
+                } else {
+                    Self::entity(project, &entities, entity)
+                },
+            ));
+        }
+
+        files.push(File::new(format!("{name}/src/index.ts"), index));
+
+        files
+    }
+}
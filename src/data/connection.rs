@@ -67,6 +67,10 @@ pub struct Connection {
     pub to: Relation,
     pub points: Vec<(i32, i32)>,
 
+    /// Type arguments of a generic parent or interface (e.g. `User` in `Repository<User>`)
+    #[serde(default)]
+    pub arguments: Vec<String>,
+
     #[serde(skip)]
     pub data: ConnectionItemData,
 }
@@ -87,6 +91,7 @@ impl Connection {
             to,
             data: ConnectionItemData::new(&points, start, end),
             points,
+            arguments: vec![],
         }
     }
 
@@ -122,6 +127,14 @@ impl Connection {
         self.update_data(Some(PathUpdate::RemovePoint(index)));
     }
 
+    /// Moves all the explicit points by the given offset
+    pub fn translate_points(&mut self, offset: Point) {
+        for index in 0..self.points.len() {
+            let point = Point::from(self.points[index]) + offset;
+            self.update_point(index, point);
+        }
+    }
+
     pub fn update_origin(&mut self, entity: EntityKey, rect: Rect, reset: bool) -> bool {
         self.update_data(if self.from.entity == entity {
             Some(PathUpdate::MoveStartRect(rect, reset))
@@ -168,13 +181,9 @@ impl Connection {
             Some(PathUpdate::MoveStartRect(rect, reset)) => {
                 Self::animate_property(&mut self.data.start_rect, rect, reset)
             }
-            Some(PathUpdate::AddPoint(index, point)) => {
-                if index <= self.points.len() {
-                    self.points.insert(index, point.into());
-                    true
-                } else {
-                    false
-                }
+            Some(PathUpdate::AddPoint(index, point)) if index <= self.points.len() => {
+                self.points.insert(index, point.into());
+                true
             }
             Some(PathUpdate::MovePoint(index, value)) => {
                 let value = value.into();
@@ -186,17 +195,13 @@ impl Connection {
                     false
                 }
             }
-            Some(PathUpdate::RemovePoint(index)) => {
-                if index < self.points.len() {
-                    let point = Point::from(self.points.remove(index));
-                    if self.data.ghost_point == Some(point) {
-                        self.data.ghost_point = None;
-                    }
-
-                    true
-                } else {
-                    false
+            Some(PathUpdate::RemovePoint(index)) if index < self.points.len() => {
+                let point = Point::from(self.points.remove(index));
+                if self.data.ghost_point == Some(point) {
+                    self.data.ghost_point = None;
                 }
+
+                true
             }
             Some(PathUpdate::MoveEndRect(rect, reset)) => {
                 Self::animate_property(&mut self.data.end_rect, rect, reset)
@@ -243,8 +248,10 @@ impl Connection {
         }
     }
 
-    fn render_arrow(&self, c: &mut Canvas, affine: Affine, color: Color, stroke: &Stroke) {
-        let points = &self.data.path_points;
+    /// Builds the arrow head at the end of the path (in grid units).
+    ///
+    /// Returns the path and whether it should be filled instead of stroked.
+    pub fn arrow_path(&self, points: &[PathPoint]) -> (BezPath, bool) {
         let relation = &self.relation;
 
         let head = Point::from(&points[points.len() - 1]);
@@ -286,15 +293,11 @@ impl Connection {
             }
         }
 
-        match relation {
-            RelationType::Composition => c.scene().fill(Fill::NonZero, affine, color, None, &path),
-            _ => c.scene().stroke(stroke, affine, color, None, &path),
-        }
+        (path, *relation == RelationType::Composition)
     }
 
-    fn render_icon(&self, c: &mut Canvas, pos: Vec2, scale: f64, line_color: Color, reverse: bool) {
-        let points = &self.data.path_points;
-
+    /// Computes the rect of the multiplicity icon at the end (or the start if reversed) of the path (in grid units)
+    pub fn icon_rect(&self, points: &[PathPoint], reverse: bool) -> (Rect, &Multiplicity) {
         let (head, tail, multiplicity, offset) = if reverse {
             (
                 Point::from(&points[0]),
@@ -321,34 +324,18 @@ impl Connection {
             head - rect / 2. - direction.rotate_by_angle(FRAC_PI_4) * 0.4
         };
 
-        let rect = (Rect::new(origin, rect) * scale).translate(-pos);
-        Icon::new(Symbol::from(multiplicity), rect, size * scale, line_color).draw(c);
-    }
-}
-
-impl Item for Connection {
-    fn update(&mut self, state: &State, ws: &Workspace) -> bool {
-        let highlighted = state.selected_point.is_some_and(|(key, _)| key == self.key)
-            || ws.hovered_connection == Some(self.key)
-            || [self.from.entity, self.to.entity].iter().any(|&entity| {
-                ws.hovered_entity == Some(entity) || state.selected_entity == Some(entity)
-            });
-
-        self.data.opacity.set(if highlighted { 0.8 } else { 0.5 });
-
-        self.update_data(None)
+        (Rect::new(origin, rect), multiplicity)
     }
 
-    fn render(&self, c: &mut Canvas, state: &State, ws: &Workspace) {
+    /// Renders the line along with the arrow and multiplicity icons (without the editing points)
+    pub fn render_path(&self, c: &mut Canvas, ws: &Workspace) {
         let pos = ws.position();
         let scale = c.scale();
-        let zoom_adjustment = ws.zoom();
-        let zoom = zoom_adjustment * Workspace::GRID_SIZE;
+        let zoom = ws.zoom() * Workspace::GRID_SIZE;
 
         let affine = Affine::scale(zoom * scale).then_translate((-pos * scale).into());
 
         let line_color = c.colors().text.multiply_alpha(*self.data.opacity);
-        let accent_color = c.colors().accent;
         let stroke = Stroke::new(ConnectionItemData::STROKE_THICKNESS)
             .with_caps(Cap::Butt)
             .with_join(Join::Round);
@@ -376,6 +363,48 @@ impl Item for Connection {
         // Draw icons
         self.render_icon(c, pos, zoom, line_color, false);
         self.render_icon(c, pos, zoom, line_color, true);
+    }
+
+    fn render_arrow(&self, c: &mut Canvas, affine: Affine, color: Color, stroke: &Stroke) {
+        let (path, fill) = self.arrow_path(&self.data.path_points);
+
+        if fill {
+            c.scene().fill(Fill::NonZero, affine, color, None, &path)
+        } else {
+            c.scene().stroke(stroke, affine, color, None, &path)
+        }
+    }
+
+    fn render_icon(&self, c: &mut Canvas, pos: Vec2, scale: f64, line_color: Color, reverse: bool) {
+        let (rect, multiplicity) = self.icon_rect(&self.data.path_points, reverse);
+
+        let rect = (rect * scale).translate(-pos);
+        Icon::new(Symbol::from(multiplicity), rect, rect.size.x, line_color).draw(c);
+    }
+}
+
+impl Item for Connection {
+    fn update(&mut self, state: &State, ws: &Workspace) -> bool {
+        let highlighted = state.selected_point.is_some_and(|(key, _)| key == self.key)
+            || ws.hovered_connection == Some(self.key)
+            || [self.from.entity, self.to.entity]
+                .iter()
+                .any(|&entity| ws.hovered_entity == Some(entity) || state.is_selected(entity));
+
+        self.data.opacity.set(if highlighted { 0.8 } else { 0.5 });
+
+        self.update_data(None)
+    }
+
+    fn render(&self, c: &mut Canvas, state: &State, ws: &Workspace) {
+        self.render_path(c, ws);
+
+        let scale = c.scale();
+        let zoom_adjustment = ws.zoom();
+        let affine = Affine::scale(zoom_adjustment * Workspace::GRID_SIZE * scale)
+            .then_translate((-ws.position() * scale).into());
+
+        let accent_color = c.colors().accent;
 
         let mut render_point = |point: Point, accent: Color, border: Color| {
             for (color, radius) in &[(accent, 0.22), (border, 0.20), (accent, 0.14)] {
@@ -0,0 +1,154 @@
+use crate::data::connection::{Multiplicity, RelationType};
+use crate::data::entity::{EntityType, TypeParameter};
+use crate::data::project::{EntityKey, Project};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+const INDENT: &str = "    ";
+
+fn multiplicity(multiplicity: &Multiplicity) -> &'static str {
+    match multiplicity {
+        Multiplicity::One => "1",
+        Multiplicity::Many => "*",
+    }
+}
+
+/// Mermaid uses tildes for generics (e.g. `List~int~`)
+fn escape(member: String) -> String {
+    member.trim_end().replace(['<', '>'], "~")
+}
+
+/// Mermaid only allows alphanumeric ids, so every entity gets a unique one (the name is used as a label if needed)
+fn ids(project: &Project) -> HashMap<EntityKey, String> {
+    let mut ids = HashMap::new();
+    let mut used = HashSet::new();
+
+    for &key in &project.ordered_entities {
+        let mut id = project.entities[key]
+            .name
+            .trim()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect::<String>();
+
+        if !id.starts_with(char::is_alphabetic) {
+            id.insert(0, 'C');
+        }
+
+        let mut unique = id.clone();
+        let mut i = 1;
+
+        while !used.insert(unique.clone()) {
+            i += 1;
+            unique = format!("{id}{i}");
+        }
+
+        ids.insert(key, unique);
+    }
+
+    ids
+}
+
+/// Serializes the project into a Mermaid class diagram
+pub fn serialize(project: &Project) -> String {
+    let ids = ids(project);
+    let mut output = String::new();
+
+    if !project.name.trim().is_empty() {
+        _ = writeln!(output, "---\ntitle: {}\n---", project.name.trim());
+    }
+
+    output += "classDiagram\n";
+
+    for &key in &project.ordered_entities {
+        let entity = &project.entities[key];
+        let id = &ids[&key];
+
+        _ = write!(output, "{INDENT}class {id}");
+
+        // Constraints can't be shown, only the names of the type parameters
+        if !entity.generics.is_empty() {
+            output += &escape(TypeParameter::list(&entity.generics));
+        }
+
+        if entity.name.trim() != id {
+            _ = write!(output, "[\"{}\"]", entity.name.trim().replace('"', "'"));
+        }
+
+        let annotation = match entity.entity_type {
+            EntityType::Class => None,
+            EntityType::AbstractClass => Some("abstract"),
+            EntityType::SealedClass => Some("sealed"),
+            EntityType::Interface => Some("interface"),
+            EntityType::Enum => Some("enumeration"),
+        };
+
+        let sections = entity.sections();
+
+        if annotation.is_none() && sections.iter().all(|section| section.is_empty()) {
+            output.push('\n');
+            continue;
+        }
+
+        output += " {\n";
+
+        if let Some(annotation) = annotation {
+            _ = writeln!(output, "{INDENT}{INDENT}<<{annotation}>>");
+        }
+
+        for member in sections.into_iter().flatten() {
+            // Classifiers are written after the member
+            let classifier = match (member.is_static, member.is_abstract) {
+                (true, _) => "$",
+                (_, true) => "*",
+                _ => "",
+            };
+
+            _ = writeln!(
+                output,
+                "{INDENT}{INDENT}{}{classifier}",
+                escape(member.text)
+            );
+        }
+
+        _ = writeln!(output, "{INDENT}}}");
+    }
+
+    for connection in project.connections.values() {
+        let from = &ids[&connection.from.entity];
+        let to = &ids[&connection.to.entity];
+
+        let from_multiplicity = multiplicity(&connection.from.multiplicity);
+        let to_multiplicity = multiplicity(&connection.to.multiplicity);
+
+        // Type arguments of a generic base are shown as the label (e.g. `Repository~User~`)
+        let label = match connection.arguments.is_empty() {
+            true => String::new(),
+            false => format!(" : {}", escape(project.reference(connection.key))),
+        };
+
+        // Arrow heads (and diamonds) point to the "to" side
+        _ = match connection.relation {
+            RelationType::Generalization => writeln!(output, "{INDENT}{to} <|-- {from}{label}"),
+            RelationType::Realization => writeln!(output, "{INDENT}{from} ..|> {to}{label}"),
+            RelationType::Association => writeln!(
+                output,
+                "{INDENT}{from} \"{from_multiplicity}\" -- \"{to_multiplicity}\" {to}"
+            ),
+            RelationType::OneWayAssociation => writeln!(
+                output,
+                "{INDENT}{from} \"{from_multiplicity}\" --> \"{to_multiplicity}\" {to}"
+            ),
+            RelationType::Aggregation => writeln!(
+                output,
+                "{INDENT}{to} \"{to_multiplicity}\" o-- \"{from_multiplicity}\" {from}"
+            ),
+            RelationType::Composition => writeln!(
+                output,
+                "{INDENT}{to} \"{to_multiplicity}\" *-- \"{from_multiplicity}\" {from}"
+            ),
+        };
+    }
+
+    output
+}
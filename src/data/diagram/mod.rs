@@ -0,0 +1,54 @@
+use super::Project;
+use std::fmt::{self, Display};
+
+pub mod mermaid;
+pub mod painter;
+pub mod plantuml;
+pub mod svg;
+
+/// Text based diagram formats (including the vector image)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Diagram {
+    PlantUml,
+    Mermaid,
+    Svg,
+}
+
+impl Diagram {
+    pub const ALL: [Diagram; 3] = [Diagram::PlantUml, Diagram::Mermaid, Diagram::Svg];
+
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "puml" | "plantuml" | "pu" => Some(Diagram::PlantUml),
+            "mmd" | "mermaid" => Some(Diagram::Mermaid),
+            "svg" => Some(Diagram::Svg),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Diagram::PlantUml => "puml",
+            Diagram::Mermaid => "mmd",
+            Diagram::Svg => "svg",
+        }
+    }
+
+    pub fn serialize(self, project: &Project) -> String {
+        match self {
+            Diagram::PlantUml => plantuml::serialize(project),
+            Diagram::Mermaid => mermaid::serialize(project),
+            Diagram::Svg => svg::serialize(project),
+        }
+    }
+}
+
+impl Display for Diagram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Diagram::PlantUml => write!(f, "PlantUML"),
+            Diagram::Mermaid => write!(f, "Mermaid"),
+            Diagram::Svg => write!(f, "SVG"),
+        }
+    }
+}
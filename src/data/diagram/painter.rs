@@ -0,0 +1,323 @@
+use crate::data::connection::RelationType;
+use crate::data::entity::{EntityType, Line};
+use crate::data::project::{EntityKey, Project};
+use crate::data::Entity;
+use crate::elements::primitives::icon::Symbol;
+use crate::elements::primitives::text::Text;
+use crate::elements::workspace::connection::ConnectionItemData;
+use crate::elements::workspace::entity::{bounds, template, TEMPLATE_DASHES};
+use crate::elements::workspace::Workspace;
+use crate::geometry::{Point, Rect};
+use crate::presentation::{fonts, Colors, FontResource};
+use skrifa::instance::Size;
+use skrifa::outline::{DrawSettings, OutlinePen};
+use skrifa::MetadataProvider;
+use std::collections::HashMap;
+use vello::kurbo::{self, Affine, BezPath, Shape};
+use vello::peniko::Color;
+
+/// Opacity of the connections, when they aren't highlighted
+const CONNECTION_OPACITY: f32 = 0.5;
+
+/// Dash and gap length of the realization lines (in pixels)
+pub const DASHES: [f64; 2] = [0.75 * Workspace::GRID_SIZE, 0.5 * Workspace::GRID_SIZE];
+
+pub struct TextStyle {
+    pub size: f64,
+    pub bold: bool,
+    pub italic: bool,
+}
+
+impl TextStyle {
+    /// The font used by the workspace for this style
+    pub fn font(&self) -> &'static FontResource<'static> {
+        match (self.bold, self.italic) {
+            (true, true) => fonts::jbmono_bold_italic(),
+            (true, false) => fonts::jbmono_bold(),
+            (false, true) => fonts::jbmono_italic(),
+            (false, false) => fonts::jbmono_regular(),
+        }
+    }
+}
+
+/// Draws the diagram outside of the workspace (e.g. into a vector image, or without a GPU).
+///
+/// Everything is in pixels, without zoom.
+pub trait Painter {
+    /// Rounded rectangle, optionally with a drop shadow
+    fn rect(&mut self, rect: Rect, radius: f64, color: Color, shadow: Option<Color>);
+
+    /// A single line of text, with the baseline at the origin
+    fn text(&mut self, text: &str, origin: Point, style: TextStyle, color: Color);
+
+    fn fill(&mut self, path: &BezPath, color: Color);
+
+    fn stroke(&mut self, path: &BezPath, color: Color, width: f64, dashed: bool);
+}
+
+/// Collects the outline of a glyph into a path (flipping the y-axis)
+struct Pen {
+    path: BezPath,
+    origin: Point,
+}
+
+impl Pen {
+    fn point(&self, x: f32, y: f32) -> Point {
+        Point::new(self.origin.x + x as f64, self.origin.y - y as f64)
+    }
+}
+
+impl OutlinePen for Pen {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.path.move_to(self.point(x, y));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.path.line_to(self.point(x, y));
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        self.path.quad_to(self.point(cx0, cy0), self.point(x, y));
+    }
+
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        self.path
+            .curve_to(self.point(cx0, cy0), self.point(cx1, cy1), self.point(x, y));
+    }
+
+    fn close(&mut self) {
+        self.path.close_path();
+    }
+}
+
+/// Outline of a line of text, with the baseline at the origin
+pub fn text_outline(text: &str, font: &FontResource, size: f64, origin: Point) -> BezPath {
+    let outlines = font.table.outline_glyphs();
+    let metrics = font.metrics(size as f32);
+
+    let mut pen = Pen {
+        path: BezPath::new(),
+        origin,
+    };
+
+    for c in text.chars() {
+        let id = font.char_map.map(c).unwrap_or_default();
+
+        if let Some(glyph) = outlines.get(id) {
+            let settings = DrawSettings::unhinted(Size::new(size as f32), &font.location);
+            _ = glyph.draw(settings, &mut pen);
+        }
+
+        pen.origin.x += metrics.advance_width(id).unwrap_or_default() as f64;
+    }
+
+    pen.path
+}
+
+fn entity(painter: &mut impl Painter, entity: &Entity, rect: Rect, colors: &Colors) {
+    // Background (the border is just a slightly bigger box behind it)
+    painter.rect(rect, 10., colors.border, Some(colors.drop_shadow));
+    painter.rect(rect.inset_uniform(2.), 8., colors.floating_background, None);
+
+    let padded = rect.inset_uniform(16.);
+
+    // Stereotype
+    let header = if let Some(stereotype) = entity.stereotype() {
+        let style = TextStyle {
+            size: 12.,
+            bold: false,
+            italic: true,
+        };
+
+        painter.text(
+            stereotype,
+            padded.origin + (0., 12.),
+            style,
+            colors.text_secondary,
+        );
+
+        12. * 1.2 + 4.
+    } else {
+        0.
+    };
+
+    // Name
+    let style = TextStyle {
+        size: 16.,
+        bold: true,
+        italic: entity.entity_type == EntityType::AbstractClass,
+    };
+
+    painter.text(
+        &entity.name,
+        padded.origin + (0., header + 16.),
+        style,
+        colors.text,
+    );
+
+    // Attributes
+    let line = 12. * 1.2 + 8.;
+    let mut y = header + 16. * 1.2;
+
+    let mut property = |member: &Line, y: f64| {
+        let style = TextStyle {
+            size: 12.,
+            bold: false,
+            italic: member.is_abstract,
+        };
+
+        let origin = padded.origin + (0., y + 12.);
+
+        // Static members are underlined
+        if member.is_static {
+            let width = Text::measure(&member.text, 12., style.font()).x;
+            painter.rect(
+                Rect::new(origin + (0., 2.), (width, 1.)),
+                0.,
+                colors.accent,
+                None,
+            );
+        }
+
+        painter.text(&member.text, origin, style, colors.accent);
+    };
+
+    for section in entity.sections().iter().filter(|s| !s.is_empty()) {
+        y += 8.;
+
+        for member in section {
+            property(member, y);
+            y += line;
+        }
+    }
+
+    // Type parameters (the painters only have solid lines, so the border is split into the dashes)
+    if let Some(template) = template(entity, rect) {
+        painter.rect(template, 4., colors.floating_background, None);
+
+        let outline = kurbo::RoundedRect::from_rect(template.into(), 4.);
+        let border = kurbo::dash(outline.path_elements(0.1), 0., &TEMPLATE_DASHES);
+        painter.stroke(&border.collect(), colors.border, 2., false);
+
+        let style = || TextStyle {
+            size: 12.,
+            bold: false,
+            italic: false,
+        };
+
+        for (i, parameter) in entity.generics.iter().enumerate() {
+            let origin = template.origin + (6., 4. + i as f64 * 12. * 1.2 + 12.);
+            painter.text(
+                &parameter.to_string(),
+                origin,
+                style(),
+                colors.text_secondary,
+            );
+        }
+    }
+}
+
+/// Paints the whole diagram, the connections first and the entities on top of them
+pub fn paint(project: &Project, colors: &Colors, painter: &mut impl Painter) {
+    let grid = Affine::scale(Workspace::GRID_SIZE);
+
+    let rects = project
+        .entities
+        .iter()
+        .map(|(key, entity)| (key, bounds(entity)))
+        .collect::<HashMap<EntityKey, Rect>>();
+
+    // Connections (in grid units)
+    let line_color = colors.text.multiply_alpha(CONNECTION_OPACITY);
+    let width = ConnectionItemData::STROKE_THICKNESS * Workspace::GRID_SIZE;
+
+    for connection in project.connections.values() {
+        let (Some(&start), Some(&end)) = (
+            rects.get(&connection.from.entity),
+            rects.get(&connection.to.entity),
+        ) else {
+            continue;
+        };
+
+        let data = ConnectionItemData::new(
+            &connection.points,
+            start / Workspace::GRID_SIZE,
+            end / Workspace::GRID_SIZE,
+        );
+
+        if data.path_points.len() < 2 {
+            continue;
+        }
+
+        // Line
+        let dashed = connection.relation == RelationType::Realization;
+        painter.stroke(&(grid * data.path.clone()), line_color, width, dashed);
+
+        // Arrow
+        let (arrow, fill) = connection.arrow_path(&data.path_points);
+        let arrow = grid * arrow;
+
+        if fill {
+            painter.fill(&arrow, line_color);
+        } else {
+            painter.stroke(&arrow, line_color, width, false);
+        }
+
+        // Multiplicity icons
+        for reverse in [false, true] {
+            let (rect, multiplicity) = connection.icon_rect(&data.path_points, reverse);
+            let rect = rect * Workspace::GRID_SIZE;
+
+            // The baseline is at the bottom of the icon (like `Text` draws it)
+            let icon = text_outline(
+                &char::from(Symbol::from(multiplicity)).to_string(),
+                fonts::icons(),
+                rect.size.x,
+                rect.origin + (0., rect.size.x),
+            );
+
+            painter.fill(&icon, line_color);
+        }
+    }
+
+    // Entities
+    for key in &project.ordered_entities {
+        if let (Some(entity), Some(&rect)) = (project.entities.get(*key), rects.get(key)) {
+            self::entity(painter, entity, rect, colors);
+        }
+    }
+}
+
+/// Collects the bounding box of everything that's painted (except the text, which is inside the entities)
+#[derive(Default)]
+struct Bounds(Option<kurbo::Rect>);
+
+impl Bounds {
+    fn include(&mut self, rect: kurbo::Rect) {
+        self.0 = Some(self.0.map_or(rect, |bb| bb.union(rect)));
+    }
+}
+
+impl Painter for Bounds {
+    fn rect(&mut self, rect: Rect, _: f64, _: Color, _: Option<Color>) {
+        self.include(rect.into());
+    }
+
+    fn text(&mut self, _: &str, _: Point, _: TextStyle, _: Color) {}
+
+    fn fill(&mut self, path: &BezPath, _: Color) {
+        self.include(path.bounding_box());
+    }
+
+    fn stroke(&mut self, path: &BezPath, _: Color, width: f64, _: bool) {
+        self.include(path.bounding_box().inflate(width / 2., width / 2.));
+    }
+}
+
+/// The bounding box of the painted diagram (in pixels)
+pub fn bounding_box(project: &Project) -> Option<Rect> {
+    let mut bounds = Bounds::default();
+    paint(project, &Colors::LIGHT, &mut bounds);
+
+    bounds.0.map(Rect::from)
+}
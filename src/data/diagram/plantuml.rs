@@ -0,0 +1,137 @@
+use crate::data::connection::{Multiplicity, RelationType};
+use crate::data::entity::{EntityType, TypeParameter};
+use crate::data::project::{ConnectionKey, EntityKey, Project};
+use std::fmt::Write;
+
+/// Names, which aren't valid identifiers, have to be quoted
+fn name(project: &Project, key: EntityKey) -> String {
+    let name = project.entities[key].name.trim();
+
+    if !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+    {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('"', ""))
+    }
+}
+
+/// Type parameters in the syntax of Java (e.g. `<T extends IEntity>`), which PlantUML shows as is
+fn generics(parameters: &[TypeParameter]) -> String {
+    if parameters.is_empty() {
+        return String::new();
+    }
+
+    let parameters = parameters.iter().map(|p| match p.constraints.is_empty() {
+        true => p.name.clone(),
+        false => format!("{} extends {}", p.name, p.constraints.join(" & ")),
+    });
+
+    format!("<{}>", parameters.collect::<Vec<_>>().join(", "))
+}
+
+/// The base with its type arguments (e.g. `Repository<User>`)
+fn reference(project: &Project, connection: ConnectionKey) -> String {
+    let arguments = &project.connections[connection].arguments;
+    let name = name(project, project.connections[connection].to.entity);
+
+    match arguments.is_empty() {
+        true => name,
+        false => format!("{name}<{}>", arguments.join(", ")),
+    }
+}
+
+fn multiplicity(multiplicity: &Multiplicity) -> &'static str {
+    match multiplicity {
+        Multiplicity::One => "1",
+        Multiplicity::Many => "*",
+    }
+}
+
+/// Serializes the project into a PlantUML class diagram
+pub fn serialize(project: &Project) -> String {
+    let mut output = String::from("@startuml\n");
+
+    if !project.name.trim().is_empty() {
+        _ = writeln!(output, "title {}", project.name.trim());
+    }
+
+    for &key in &project.ordered_entities {
+        let entity = &project.entities[key];
+        let name = name(project, key) + &generics(&entity.generics);
+
+        _ = match entity.entity_type {
+            EntityType::Class => write!(output, "\nclass {name}"),
+            EntityType::AbstractClass => write!(output, "\nabstract class {name}"),
+            EntityType::SealedClass => write!(output, "\nclass {name} <<sealed>>"),
+            EntityType::Interface => write!(output, "\ninterface {name}"),
+            EntityType::Enum => write!(output, "\nenum {name}"),
+        };
+
+        // Bases are declared with the class, so the type arguments aren't lost (e.g. `extends Repository<User>`)
+        if let Some(parent) = entity.parent {
+            _ = write!(output, " extends {}", reference(project, parent));
+        }
+
+        if !entity.implements.is_empty() {
+            let interfaces = entity
+                .implements
+                .iter()
+                .map(|&conn| reference(project, conn))
+                .collect::<Vec<_>>();
+
+            _ = write!(output, " implements {}", interfaces.join(", "));
+        }
+
+        let sections = entity.sections();
+
+        if sections.iter().all(|section| section.is_empty()) {
+            output.push('\n');
+            continue;
+        }
+
+        output += " {\n";
+
+        for member in sections.into_iter().flatten() {
+            let classifier = match (member.is_static, member.is_abstract) {
+                (true, _) => "{static} ",
+                (_, true) => "{abstract} ",
+                _ => "",
+            };
+
+            _ = writeln!(output, "  {classifier}{}", member.text.trim_end());
+        }
+
+        output += "}\n";
+    }
+
+    if !project.connections.is_empty() {
+        output.push('\n');
+    }
+
+    for connection in project.connections.values() {
+        let from = name(project, connection.from.entity);
+        let to = name(project, connection.to.entity);
+
+        // The arrow head (or diamond) always points to the "to" side
+        let arrow = match connection.relation {
+            // Declared with the class
+            RelationType::Generalization | RelationType::Realization => continue,
+            RelationType::Association => "--",
+            RelationType::OneWayAssociation => "-->",
+            RelationType::Aggregation => "--o",
+            RelationType::Composition => "--*",
+        };
+
+        _ = writeln!(
+            output,
+            "{from} \"{}\" {arrow} \"{}\" {to}",
+            multiplicity(&connection.from.multiplicity),
+            multiplicity(&connection.to.multiplicity)
+        );
+    }
+
+    output += "@enduml\n";
+    output
+}
@@ -0,0 +1,151 @@
+use super::painter::{self, Painter, TextStyle, DASHES};
+use crate::data::project::Project;
+use crate::geometry::{Point, Rect};
+use crate::presentation::Colors;
+use std::fmt::Write;
+use vello::kurbo::BezPath;
+use vello::peniko::Color;
+
+/// Space around the diagram (in pixels)
+const PADDING: f64 = 32.;
+
+/// Color and opacity attributes (e.g. `fill="#000000" fill-opacity="0.5"`)
+fn paint(attribute: &str, color: Color) -> String {
+    let color = color.to_rgba8();
+    let mut paint = format!(
+        "{attribute}=\"#{:02x}{:02x}{:02x}\"",
+        color.r, color.g, color.b
+    );
+
+    if color.a != 255 {
+        _ = write!(
+            paint,
+            " {attribute}-opacity=\"{:.3}\"",
+            color.a as f64 / 255.
+        );
+    }
+
+    paint
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Writes the painted shapes as SVG elements
+struct Svg(String);
+
+impl Painter for Svg {
+    fn rect(&mut self, rect: Rect, radius: f64, color: Color, shadow: Option<Color>) {
+        let filter = if shadow.is_some() {
+            " filter=\"url(#shadow)\""
+        } else {
+            ""
+        };
+
+        _ = writeln!(
+            self.0,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{radius}\" {}{filter}/>",
+            rect.origin.x,
+            rect.origin.y,
+            rect.size.x,
+            rect.size.y,
+            paint("fill", color)
+        );
+    }
+
+    fn text(&mut self, text: &str, origin: Point, style: TextStyle, color: Color) {
+        let weight = if style.bold {
+            " font-weight=\"bold\""
+        } else {
+            ""
+        };
+
+        let italic = if style.italic {
+            " font-style=\"italic\""
+        } else {
+            ""
+        };
+
+        _ = writeln!(
+            self.0,
+            "<text x=\"{}\" y=\"{}\" font-size=\"{}\"{weight}{italic} {}>{}</text>",
+            origin.x,
+            origin.y,
+            style.size,
+            paint("fill", color),
+            escape(text)
+        );
+    }
+
+    fn fill(&mut self, path: &BezPath, color: Color) {
+        _ = writeln!(
+            self.0,
+            "<path d=\"{}\" {}/>",
+            path.to_svg(),
+            paint("fill", color)
+        );
+    }
+
+    fn stroke(&mut self, path: &BezPath, color: Color, width: f64, dashed: bool) {
+        let dashes = if dashed {
+            format!(
+                " stroke-linecap=\"round\" stroke-dasharray=\"{} {}\"",
+                DASHES[0], DASHES[1]
+            )
+        } else {
+            String::new()
+        };
+
+        _ = writeln!(
+            self.0,
+            "<path d=\"{}\" fill=\"none\" {} stroke-width=\"{width}\" stroke-linejoin=\"round\"{dashes}/>",
+            path.to_svg(),
+            paint("stroke", color)
+        );
+    }
+}
+
+/// Renders the whole diagram into an SVG image, cropped to its bounding box
+pub fn serialize(project: &Project) -> String {
+    let colors = &Colors::LIGHT;
+
+    let mut svg = Svg(String::new());
+    painter::paint(project, colors, &mut svg);
+
+    let view = painter::bounding_box(project)
+        .unwrap_or_default()
+        .inset_uniform(-PADDING);
+
+    let mut output = String::new();
+
+    _ = writeln!(
+        output,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"{} {} {} {}\">",
+        view.size.x.ceil(),
+        view.size.y.ceil(),
+        view.origin.x,
+        view.origin.y,
+        view.size.x.ceil(),
+        view.size.y.ceil()
+    );
+
+    let shadow = colors.drop_shadow.to_rgba8();
+    _ = writeln!(
+        output,
+        "<defs><filter id=\"shadow\" x=\"-10%\" y=\"-10%\" width=\"120%\" height=\"120%\"><feDropShadow dx=\"0\" dy=\"1\" stdDeviation=\"2.5\" flood-color=\"#{:02x}{:02x}{:02x}\" flood-opacity=\"{:.3}\"/></filter></defs>",
+        shadow.r,
+        shadow.g,
+        shadow.b,
+        shadow.a as f64 / 255.
+    );
+
+    output += "<g font-family=\"'JetBrains Mono', monospace\" xml:space=\"preserve\">\n";
+    output += &svg.0;
+    output += "</g>\n</svg>\n";
+
+    output
+}
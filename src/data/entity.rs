@@ -10,7 +10,7 @@ use std::fmt::Display;
 use std::fmt::Formatter;
 use std::vec;
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
 pub enum AccessModifier {
     #[default]
     Public,
@@ -38,11 +38,122 @@ impl AsToken for AccessModifier {
     }
 }
 
+/// Whether the value of a field can change
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub enum Mutability {
+    #[default]
+    Mutable = 0,
+
+    /// Can only be assigned in the constructor
+    ReadOnly = 1,
+
+    /// Known at compile time (and implicitly static)
+    Const = 2,
+
+    /// Can only be assigned when the object is created (an `init` accessor, fields are read-only)
+    Init = 3,
+}
+
+impl AsToken for Mutability {
+    fn as_token(&self) -> Vec<Token> {
+        match self {
+            Mutability::Mutable => vec![],
+            Mutability::ReadOnly | Mutability::Init => vec![Token::Keyword(Keyword::Readonly)],
+            Mutability::Const => vec![Token::Keyword(Keyword::Const)],
+        }
+    }
+}
+
+impl Display for Mutability {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mutability::Mutable => write!(f, "Mutable"),
+            Mutability::ReadOnly => write!(f, "Read-only"),
+            Mutability::Const => write!(f, "Constant"),
+            Mutability::Init => write!(f, "Init-only"),
+        }
+    }
+}
+
+/// How a method takes part in inheritance
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub enum Inheritance {
+    #[default]
+    None = 0,
+
+    /// Declared without a body, the subclasses have to implement it
+    Abstract = 1,
+
+    /// Can be overridden by the subclasses
+    Virtual = 2,
+
+    /// Overrides an abstract or virtual method of the parent
+    Override = 3,
+}
+
+impl AsToken for Inheritance {
+    fn as_token(&self) -> Vec<Token> {
+        match self {
+            Inheritance::None => vec![],
+            Inheritance::Abstract => vec![Token::Keyword(Keyword::Abstract)],
+            Inheritance::Virtual => vec![Token::Keyword(Keyword::Virtual)],
+            Inheritance::Override => vec![Token::Keyword(Keyword::Override)],
+        }
+    }
+}
+
+impl Display for Inheritance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Inheritance::None => write!(f, "None"),
+            Inheritance::Abstract => write!(f, "Abstract"),
+            Inheritance::Virtual => write!(f, "Virtual"),
+            Inheritance::Override => write!(f, "Override"),
+        }
+    }
+}
+
+/// Whether a field stores the value directly or through accessors
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub enum FieldKind {
+    Field = 0,
+
+    /// An auto-property, the mutability decides which accessors it has
+    #[default]
+    Property = 1,
+}
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Field {
     pub name: String,
     pub modifier: AccessModifier,
     pub r#type: String,
+    #[serde(default)]
+    pub is_static: bool,
+    #[serde(default)]
+    pub mutability: Mutability,
+    #[serde(default)]
+    pub kind: FieldKind,
+}
+
+impl Field {
+    /// Whether it's declared with accessors (constants are always plain fields)
+    pub fn is_property(&self) -> bool {
+        self.kind == FieldKind::Property && self.mutability != Mutability::Const
+    }
+
+    /// The accessors of a property (e.g. `get, set`)
+    pub fn accessors(&self) -> Option<&'static str> {
+        if !self.is_property() {
+            return None;
+        }
+
+        Some(match self.mutability {
+            Mutability::ReadOnly | Mutability::Const => "get",
+            Mutability::Init => "get, init",
+            Mutability::Mutable => "get, set",
+        })
+    }
 }
 
 impl Display for Field {
@@ -53,78 +164,408 @@ impl Display for Field {
             self.modifier.as_char(),
             self.name,
             self.r#type
-        )
+        )?;
+
+        match (self.accessors(), self.mutability) {
+            (Some(accessors), _) => write!(f, " «{accessors}»"),
+            (None, Mutability::Mutable) => Ok(()),
+            (None, _) => write!(f, " {{readOnly}}"),
+        }
     }
 }
 
 impl AsToken for Field {
     fn as_token(&self) -> Vec<Token> {
-        [
-            self.modifier.as_token(),
-            vec![
-                Token::Identifier(self.r#type.clone()),
+        let mut tokens = self.modifier.as_token();
+
+        // Constants are static without the keyword
+        if self.is_static && self.mutability != Mutability::Const {
+            tokens.push(Token::Keyword(Keyword::Static));
+        }
+
+        // Properties can't be read-only, they just don't have a setter
+        if !self.is_property() {
+            tokens.append(&mut self.mutability.as_token());
+        }
+
+        tokens.extend([
+            Token::Identifier(self.r#type.clone()),
+            Token::Space,
+            Token::Identifier(self.name.clone()),
+        ]);
+
+        match self.mutability {
+            _ if self.is_property() => tokens.push(Token::Accessors(self.mutability)),
+            Mutability::Mutable | Mutability::ReadOnly | Mutability::Init => {
+                tokens.push(Token::SemiColon)
+            }
+            Mutability::Const => tokens.extend([
                 Token::Space,
-                Token::Identifier(self.name.clone()),
-                Token::Accessors,
-            ],
-        ]
-        .concat()
+                Token::Identifier("=".to_string()),
+                Token::Space,
+                Token::Identifier("default".to_string()),
+                Token::SemiColon,
+            ]),
+        }
+
+        tokens
+    }
+}
+
+/// A type parameter of a generic entity or method (e.g. `T` in `Repository<T>`)
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct TypeParameter {
+    pub name: String,
+
+    /// Types the argument has to inherit from or implement (e.g. `where T : IEntity`)
+    #[serde(default)]
+    pub constraints: Vec<String>,
+}
+
+impl TypeParameter {
+    /// Parses a parameter in the format it's displayed in (e.g. `T : IEntity, IComparable`)
+    pub fn parse(str: &str) -> Option<Self> {
+        let (name, constraints) = str.split_once(':').unwrap_or((str, ""));
+        let name = name.trim();
+
+        if name.is_empty() {
+            return None;
+        }
+
+        Some(TypeParameter {
+            name: name.to_string(),
+            constraints: constraints
+                .split(',')
+                .map(|c| c.trim().to_string())
+                .filter(|c| !c.is_empty())
+                .collect(),
+        })
+    }
+
+    /// The names of the parameters in angle brackets (e.g. `<T, U>`), or nothing if there are none
+    pub fn list(parameters: &[TypeParameter]) -> String {
+        if parameters.is_empty() {
+            return String::new();
+        }
+
+        let names = parameters.iter().map(|p| p.name.as_str());
+        format!("<{}>", names.collect::<Vec<_>>().join(", "))
+    }
+}
+
+impl Display for TypeParameter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.constraints.is_empty() {
+            true => write!(f, "{}", self.name),
+            false => write!(f, "{} : {}", self.name, self.constraints.join(", ")),
+        }
+    }
+}
+
+/// The constraint clauses (e.g. ` where T : IEntity`) of the parameters that have any
+impl AsToken for [TypeParameter] {
+    fn as_token(&self) -> Vec<Token> {
+        self.iter()
+            .filter(|p| !p.constraints.is_empty())
+            .flat_map(|p| [Token::Space, Token::Identifier(format!("where {p}"))])
+            .collect()
+    }
+}
+
+/// How an argument is passed to a method
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub enum Direction {
+    /// Passed by value
+    #[default]
+    In = 0,
+
+    /// Passed by reference, the method can change it
+    Ref = 1,
+
+    /// Assigned by the method
+    Out = 2,
+
+    /// Takes any number of arguments as an array (only the last parameter)
+    Params = 3,
+}
+
+impl Direction {
+    pub fn keyword(&self) -> Option<&'static str> {
+        match self {
+            Direction::In => None,
+            Direction::Ref => Some("ref"),
+            Direction::Out => Some("out"),
+            Direction::Params => Some("params"),
+        }
+    }
+}
+
+impl AsToken for Direction {
+    fn as_token(&self) -> Vec<Token> {
+        match self {
+            Direction::In => vec![],
+            Direction::Ref => vec![Token::Keyword(Keyword::Ref)],
+            Direction::Out => vec![Token::Keyword(Keyword::Out)],
+            Direction::Params => vec![Token::Keyword(Keyword::Params)],
+        }
     }
 }
 
+/// A parameter of a method (e.g. `ref count: int = 0`)
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct Parameter {
+    pub name: String,
+    pub r#type: String,
+
+    /// The value used when the argument is omitted
+    #[serde(default)]
+    pub default: Option<String>,
+    #[serde(default)]
+    pub direction: Direction,
+}
+
+impl Parameter {
+    /// Parses a parameter in the format it's displayed in (e.g. `out value: T`).
+    ///
+    /// Without a colon, the whole declaration is the type (and the parameter doesn't have a name).
+    pub fn parse(str: &str) -> Option<Self> {
+        let str = str.trim();
+
+        let (direction, declaration) = match str.split_once(char::is_whitespace) {
+            Some(("ref", rest)) => (Direction::Ref, rest),
+            Some(("out", rest)) => (Direction::Out, rest),
+            Some(("params", rest)) => (Direction::Params, rest),
+            _ => (Direction::In, str),
+        };
+
+        let (declaration, default) = match declaration.split_once('=') {
+            Some((declaration, default)) => (declaration, Some(default.trim().to_string())),
+            None => (declaration, None),
+        };
+
+        let (name, r#type) = declaration.split_once(':').unwrap_or(("", declaration));
+
+        if name.trim().is_empty() && r#type.trim().is_empty() {
+            return None;
+        }
+
+        Some(Parameter {
+            name: name.trim().to_string(),
+            r#type: r#type.trim().to_string(),
+            default: default.filter(|d| !d.is_empty()),
+            direction,
+        })
+    }
+
+    /// The parameters separated by commas
+    pub fn list(parameters: &[Parameter]) -> String {
+        let parameters = parameters.iter().map(Parameter::to_string);
+        parameters.collect::<Vec<_>>().join(", ")
+    }
+
+    /// The name, or a generated one if it doesn't have any (`arg0`, `arg1`, ...)
+    pub fn name_or_index(&self, index: usize) -> String {
+        match self.name.trim() {
+            "" => format!("arg{index}"),
+            name => name.to_string(),
+        }
+    }
+}
+
+impl Display for Parameter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(keyword) = self.direction.keyword() {
+            write!(f, "{keyword} ")?;
+        }
+
+        match self.name.is_empty() {
+            true => write!(f, "{}", self.r#type)?,
+            false => write!(f, "{}: {}", self.name, self.r#type)?,
+        }
+
+        match &self.default {
+            Some(default) => write!(f, " = {default}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The parameter list of a method (e.g. `(ref int count = 0)`)
+impl AsToken for [Parameter] {
+    fn as_token(&self) -> Vec<Token> {
+        let parameters = self.iter().enumerate().map(|(i, parameter)| {
+            let mut tokens = parameter.direction.as_token();
+            tokens.extend([
+                Token::Identifier(parameter.r#type.clone()),
+                Token::Space,
+                Token::Identifier(parameter.name_or_index(i)),
+            ]);
+
+            if let Some(default) = &parameter.default {
+                tokens.extend([
+                    Token::Space,
+                    Token::Identifier("=".to_string()),
+                    Token::Space,
+                    Token::Identifier(default.clone()),
+                ]);
+            }
+
+            tokens
+        });
+
+        vec![Token::MethodArguments(parameters.collect())]
+    }
+}
+
+/// Whether a method is an ordinary one or creates the instance
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub enum MethodKind {
+    #[default]
+    Method = 0,
+
+    /// Named after the entity, without a return type
+    Constructor = 1,
+}
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Method {
     pub name: String,
     pub modifier: AccessModifier,
     pub return_type: String,
-    pub arguments: Vec<String>,
+    pub arguments: Vec<Parameter>,
+    #[serde(default)]
+    pub generics: Vec<TypeParameter>,
+    #[serde(default)]
+    pub is_static: bool,
+    #[serde(default)]
+    pub inheritance: Inheritance,
+    #[serde(default)]
+    pub kind: MethodKind,
 }
 
-impl AsToken for Method {
-    fn as_token(&self) -> Vec<Token> {
+impl Method {
+    /// Creates a constructor of the entity with the given name
+    pub fn constructor(name: &str, arguments: Vec<Parameter>) -> Self {
+        Method {
+            name: name.to_string(),
+            arguments,
+            kind: MethodKind::Constructor,
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn is_constructor(&self) -> bool {
+        self.kind == MethodKind::Constructor
+    }
+
+    /// The declaration without the body (e.g. for interfaces)
+    pub fn signature(&self) -> Vec<Token> {
+        // Constructors can't have any other modifiers (or a return type)
+        if self.is_constructor() {
+            let mut tokens = self.modifier.as_token();
+            tokens.push(Token::Identifier(self.name.clone()));
+            tokens.append(&mut self.arguments.as_token());
+
+            return tokens;
+        }
+
+        let keywords = self
+            .is_static
+            .then_some(Token::Keyword(Keyword::Static))
+            .into_iter()
+            .chain(self.inheritance.as_token())
+            .collect();
+
         [
             self.modifier.as_token(),
+            keywords,
             vec![
                 Token::Identifier(self.return_type.clone()),
                 Token::Space,
                 Token::Identifier(if self.name.trim().is_empty() {
                     "void".to_string()
                 } else {
-                    self.name.clone()
+                    self.name.clone() + &TypeParameter::list(&self.generics)
                 }),
             ],
-            vec![Token::MethodArguments(
-                self.arguments
-                    .iter()
-                    .enumerate()
-                    .map(|(i, arg)| {
-                        (
-                            Token::Identifier(arg.clone()),
-                            Token::Identifier(format!("arg{i}")),
-                        )
-                    })
-                    .collect(),
-            )],
-            vec![Token::Block(vec![Token::NewLine, Token::Placeholder])],
+            self.arguments.as_token(),
+            self.generics.as_token(),
         ]
         .concat()
     }
 }
 
+impl AsToken for Method {
+    fn as_token(&self) -> Vec<Token> {
+        let mut tokens = self.signature();
+
+        // Abstract methods don't have a body
+        tokens.push(match self.inheritance {
+            Inheritance::Abstract => Token::SemiColon,
+            _ => Token::Block(vec![Token::NewLine, Token::Placeholder]),
+        });
+
+        tokens
+    }
+}
+
 impl Display for Method {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let args = self.arguments.join(", ");
+        let args = Parameter::list(&self.arguments);
+
+        if self.is_constructor() {
+            let modifier = self.modifier.as_char();
+            return write!(f, "{modifier}{}({args}) «create»", self.name);
+        }
+
         write!(
             f,
-            "{}{}({}): {}",
+            "{}{}{}({}): {}",
             self.modifier.as_char(),
             self.name,
+            TypeParameter::list(&self.generics),
             args,
             self.return_type
         )
     }
 }
 
+/// A named constant of an enumeration
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct Literal {
+    pub name: String,
+
+    /// Explicit value (otherwise it's numbered automatically, like in C#)
+    pub value: Option<i64>,
+}
+
+impl Display for Literal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.value {
+            Some(value) => write!(f, "{} = {value}", self.name),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+impl AsToken for Literal {
+    fn as_token(&self) -> Vec<Token> {
+        let mut tokens = vec![Token::Identifier(self.name.clone())];
+
+        if let Some(value) = self.value {
+            tokens.extend([
+                Token::Space,
+                Token::Identifier("=".to_string()),
+                Token::Space,
+                Token::Identifier(value.to_string()),
+            ]);
+        }
+
+        tokens
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
 pub enum EntityType {
     #[default]
@@ -132,6 +573,27 @@ pub enum EntityType {
     AbstractClass = 1,
     SealedClass = 2,
     Interface = 3,
+    Enum = 4,
+}
+
+impl EntityType {
+    /// Whether the entity can have a parent
+    pub fn can_inherit(&self) -> bool {
+        !matches!(self, EntityType::Interface | EntityType::Enum)
+    }
+
+    /// Whether the entity can be the parent of other entities
+    pub fn can_be_inherited(&self) -> bool {
+        !matches!(
+            self,
+            EntityType::Interface | EntityType::SealedClass | EntityType::Enum
+        )
+    }
+
+    /// Whether the entity can implement interfaces
+    pub fn can_implement(&self) -> bool {
+        *self != EntityType::Enum
+    }
 }
 
 impl AsToken for EntityType {
@@ -147,6 +609,7 @@ impl AsToken for EntityType {
                 Token::Keyword(Keyword::Class),
             ],
             EntityType::Interface => vec![Token::Keyword(Keyword::Interface)],
+            EntityType::Enum => vec![Token::Keyword(Keyword::Enum)],
         }
     }
 }
@@ -160,6 +623,7 @@ impl TryFrom<usize> for EntityType {
             1 => Ok(EntityType::AbstractClass),
             2 => Ok(EntityType::SealedClass),
             3 => Ok(EntityType::Interface),
+            4 => Ok(EntityType::Enum),
             _ => Err(()),
         }
     }
@@ -172,22 +636,38 @@ impl Display for EntityType {
             EntityType::AbstractClass => write!(f, "Abstract class"),
             EntityType::SealedClass => write!(f, "Sealed class"),
             EntityType::Interface => write!(f, "Interface"),
+            EntityType::Enum => write!(f, "Enumeration"),
         }
     }
 }
 
+/// A member (or a literal) as it's shown in the diagram
+pub struct Line {
+    pub text: String,
+
+    /// Static members are underlined
+    pub is_static: bool,
+
+    /// Abstract methods are in italics
+    pub is_abstract: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Entity {
     pub key: EntityKey,
 
     pub name: String,
     pub entity_type: EntityType,
+    #[serde(default)]
+    pub generics: Vec<TypeParameter>,
 
     pub parent: Option<ConnectionKey>, // Connection of type "Generalization"
     pub implements: Vec<ConnectionKey>, // Connections of type "Implementation"
 
     pub fields: Vec<Field>,
     pub methods: Vec<Method>,
+    #[serde(default)]
+    pub literals: Vec<Literal>, // Only used by enums
 
     pub connections: IndexSet<ConnectionKey>,
 
@@ -205,16 +685,62 @@ impl Entity {
             key: Default::default(),
             name,
             entity_type,
+            generics: vec![],
             parent: None,
             implements: vec![],
             fields: vec![],
             methods: vec![],
+            literals: vec![],
             connections: IndexSet::new(),
             position: pos,
             data: EntityItemData::new(pos),
         }
     }
 
+    /// Changes the name, the constructors are named after the entity
+    pub fn rename(&mut self, name: &str) {
+        self.name = name.to_string();
+
+        for method in self.methods.iter_mut().filter(|m| m.is_constructor()) {
+            method.name = self.name.clone();
+        }
+    }
+
+    /// Shown above the name (e.g. `<<enumeration>>`)
+    pub fn stereotype(&self) -> Option<&'static str> {
+        match self.entity_type {
+            EntityType::Enum => Some("<<enumeration>>"),
+            _ => None,
+        }
+    }
+
+    /// The members as they're shown in the diagram, in groups separated by a gap
+    /// (the fields and the methods, or the literals of an enum)
+    pub fn sections(&self) -> Vec<Vec<Line>> {
+        let literals = self.literals.iter().map(|literal| Line {
+            text: literal.to_string(),
+            is_static: false,
+            is_abstract: false,
+        });
+
+        let fields = self.fields.iter().map(|field| Line {
+            text: field.to_string(),
+            is_static: field.is_static || field.mutability == Mutability::Const,
+            is_abstract: false,
+        });
+
+        let methods = self.methods.iter().map(|method| Line {
+            text: method.to_string(),
+            is_static: method.is_static,
+            is_abstract: method.inheritance == Inheritance::Abstract,
+        });
+
+        match self.entity_type {
+            EntityType::Enum => vec![literals.collect()],
+            _ => vec![fields.collect(), methods.collect()],
+        }
+    }
+
     pub fn get_rect(&self) -> Rect {
         (*self.data.rect).translate(self.data.move_pos.unwrap_or_default()) / Workspace::GRID_SIZE
     }
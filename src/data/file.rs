@@ -0,0 +1,255 @@
+//! Project file format.
+//!
+//! Binary files start with a header (the magic number and the schema version),
+//! followed by the project encoded as MessagePack with the field names included.
+//! JSON files store the schema version in the `version` field of the root object.
+//!
+//! Both encodings are self-describing, so older files are upgraded by running the migrations
+//! on a generic [`Value`] before it's turned into the [`Project`]. Fields added with `#[serde(default)]`
+//! don't need a new version, only renames and other changes to the structure do.
+
+use super::Project;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::error::Error;
+
+/// Magic number at the start of every binary project file
+const MAGIC: [u8; 4] = *b"UMLP";
+
+/// Upgrades the project from the previous version. The first migration upgrades version 1 to version 2, and so on.
+type Migration = fn(&mut Value) -> Result<(), Box<dyn Error>>;
+
+const MIGRATIONS: &[Migration] = &[named_parameters];
+
+/// The current schema version
+pub const VERSION: u16 = MIGRATIONS.len() as u16 + 1;
+
+/// Formats a project can be saved in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Binary,
+    Json,
+}
+
+impl Format {
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "bin" => Some(Format::Binary),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Binary => "bin",
+            Format::Json => "json",
+        }
+    }
+
+    pub fn serialize(&self, project: &Project) -> Vec<u8> {
+        match self {
+            Format::Binary => to_bytes(project),
+            Format::Json => to_json(project),
+        }
+    }
+}
+
+/// Serializes the project into the binary file format
+pub fn to_bytes(project: &Project) -> Vec<u8> {
+    let mut data = MAGIC.to_vec();
+    data.extend(VERSION.to_le_bytes());
+
+    _ = rmp_serde::encode::write_named(&mut data, project);
+
+    data
+}
+
+/// The project with the schema version as the first field
+#[derive(Serialize)]
+struct Versioned<'a> {
+    version: u16,
+    #[serde(flatten)]
+    project: &'a Project,
+}
+
+/// Serializes the project into a (pretty-printed) JSON file
+pub fn to_json(project: &Project) -> Vec<u8> {
+    serde_json::to_vec_pretty(&Versioned {
+        version: VERSION,
+        project,
+    })
+    .unwrap()
+}
+
+/// Loads a binary project file (including files saved before the header was added)
+pub fn from_bytes(data: &[u8]) -> Result<Project, Box<dyn Error>> {
+    let Some(data) = data.strip_prefix(&MAGIC) else {
+        // Version 0 (raw postcard without any header)
+        let project = postcard::from_bytes::<v0::Project>(data)
+            .map_err(|_| "The file isn't a valid project")?;
+
+        return migrate(serde_json::to_value(project)?, 1);
+    };
+
+    let (Some(version), Some(body)) = (data.get(..2), data.get(2..)) else {
+        return Err("The project file is truncated".into());
+    };
+
+    // Version 0 never had a header, so it can only be a corrupt file
+    let version = u16::from_le_bytes([version[0], version[1]]);
+    if version == 0 {
+        return Err("The project file has an invalid version".into());
+    }
+
+    check(version)?;
+
+    migrate(rmp_serde::from_slice(body)?, version)
+}
+
+/// Loads a JSON project file
+pub fn from_json(data: &[u8]) -> Result<Project, Box<dyn Error>> {
+    from_value(serde_json::from_slice(data)?)
+}
+
+/// The project as a JSON value (with the version), for storing it inside of other files
+pub fn to_value(project: &Project) -> Value {
+    serde_json::to_value(Versioned {
+        version: VERSION,
+        project,
+    })
+    .unwrap()
+}
+
+/// Loads a project stored with [`to_value`] (or parsed from a JSON project file)
+pub fn from_value(mut value: Value) -> Result<Project, Box<dyn Error>> {
+    // Files without a version are from before it was added, but they have the same structure as version 1
+    let version = match value.as_object_mut().and_then(|o| o.remove("version")) {
+        Some(version) => version
+            .as_u64()
+            .and_then(|v| u16::try_from(v).ok())
+            .ok_or("The project file has an invalid version")?,
+        None => 1,
+    };
+
+    check(version)?;
+    migrate(value, version.max(1))
+}
+
+/// Fails if the file was saved by a newer version of the app
+fn check(version: u16) -> Result<(), Box<dyn Error>> {
+    if version > VERSION {
+        return Err(format!(
+            "The project was saved by a newer version of the app (file version {version}, supported up to {VERSION}). Please update the app to open it."
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Runs the migrations from the given version up to the current one
+fn migrate(mut value: Value, version: u16) -> Result<Project, Box<dyn Error>> {
+    for migration in &MIGRATIONS[version as usize - 1..] {
+        migration(&mut value)?;
+    }
+
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Version 2: Method arguments are parameters with a name instead of just the type
+fn named_parameters(value: &mut Value) -> Result<(), Box<dyn Error>> {
+    // The slot map is stored with the empty slots and the versions, so every method list is upgraded
+    fn upgrade(value: &mut Value) {
+        match value {
+            Value::Object(object) => {
+                if let Some(Value::Array(methods)) = object.get_mut("methods") {
+                    for method in methods {
+                        let Some(Value::Array(arguments)) = method.get_mut("arguments") else {
+                            continue;
+                        };
+
+                        for (i, argument) in arguments.iter_mut().enumerate() {
+                            if let Value::String(r#type) = argument {
+                                *argument = json!({ "name": format!("arg{i}"), "type": r#type });
+                            }
+                        }
+                    }
+                }
+
+                object.values_mut().for_each(upgrade);
+            }
+            Value::Array(array) => array.iter_mut().for_each(upgrade),
+            _ => {}
+        }
+    }
+
+    let entities = value
+        .get_mut("entities")
+        .ok_or("The project doesn't have any entities")?;
+
+    upgrade(entities);
+    Ok(())
+}
+
+/// The structure of the projects saved without a header. Postcard isn't self-describing,
+/// so it has to be kept as is, even when the actual structs change.
+mod v0 {
+    use crate::data::connection::{Multiplicity, RelationType};
+    use crate::data::entity::{AccessModifier, EntityType};
+    use crate::data::project::{ConnectionKey, EntityKey};
+    use indexmap::IndexSet;
+    use serde::{Deserialize, Serialize};
+    use slotmap::SlotMap;
+
+    #[derive(Serialize, Deserialize)]
+    pub struct Project {
+        name: String,
+        entities: SlotMap<EntityKey, Entity>,
+        ordered_entities: Vec<EntityKey>,
+        connections: SlotMap<ConnectionKey, Connection>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Entity {
+        key: EntityKey,
+        name: String,
+        entity_type: EntityType,
+        parent: Option<ConnectionKey>,
+        implements: Vec<ConnectionKey>,
+        fields: Vec<Field>,
+        methods: Vec<Method>,
+        connections: IndexSet<ConnectionKey>,
+        position: (i32, i32),
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Field {
+        name: String,
+        modifier: AccessModifier,
+        r#type: String,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Method {
+        name: String,
+        modifier: AccessModifier,
+        return_type: String,
+        arguments: Vec<String>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Connection {
+        key: ConnectionKey,
+        relation: RelationType,
+        from: Relation,
+        to: Relation,
+        points: Vec<(i32, i32)>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Relation {
+        entity: EntityKey,
+        multiplicity: Multiplicity,
+    }
+}
@@ -0,0 +1,116 @@
+use super::{
+    connection::RelationType,
+    project::{EntityKey, Project},
+    Connection, Entity,
+};
+use crate::elements::workspace::connection::ConnectionItemData;
+use crate::elements::workspace::entity::EntityItemData;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A part of a project, used for copying and pasting entities through the clipboard.
+///
+/// Only the connections between the copied entities are included.
+#[derive(Serialize, Deserialize)]
+pub struct Fragment<E = Entity, C = Connection> {
+    entities: Vec<E>,
+    connections: Vec<C>,
+}
+
+impl Fragment {
+    /// Grid offset between the original entities and the pasted ones
+    const OFFSET: i32 = 2;
+
+    /// Serializes the given entities (and the connections between them) into JSON
+    pub fn copy(project: &Project, entities: &[EntityKey]) -> Option<String> {
+        let fragment = Fragment {
+            entities: entities
+                .iter()
+                .filter_map(|&key| project.entities.get(key))
+                .collect(),
+            connections: project
+                .connections
+                .values()
+                .filter(|c| entities.contains(&c.from.entity) && entities.contains(&c.to.entity))
+                .collect(),
+        };
+
+        if fragment.entities.is_empty() {
+            return None;
+        }
+
+        serde_json::to_string(&fragment).ok()
+    }
+
+    /// Adds the entities from the serialized fragment to the project.
+    ///
+    /// All keys are remapped and the entities are offset, so they don't cover the existing ones.
+    /// Returns the keys of the pasted entities.
+    pub fn paste(project: &mut Project, json: &str) -> Option<Vec<EntityKey>> {
+        let fragment: Fragment = serde_json::from_str(json).ok()?;
+
+        if fragment.entities.is_empty() {
+            return None;
+        }
+
+        // Keep offsetting until none of the pasted entities lands on an existing one
+        let mut offset = 0;
+        while fragment.entities.iter().any(|entity| {
+            let position = (entity.position.0 + offset, entity.position.1 + offset);
+            project.entities.values().any(|e| e.position == position)
+        }) {
+            offset += Self::OFFSET;
+        }
+
+        let mut keys = HashMap::new();
+        let mut pasted = Vec::new();
+
+        for mut entity in fragment.entities {
+            let old = entity.key;
+
+            entity.position = (entity.position.0 + offset, entity.position.1 + offset);
+            entity.data = EntityItemData::new(entity.position);
+
+            // Connections are recreated below
+            entity.parent = None;
+            entity.implements.clear();
+            entity.connections.clear();
+
+            let key = project.add_entity(entity);
+            keys.insert(old, key);
+            pasted.push(key);
+        }
+
+        for mut connection in fragment.connections {
+            let (Some(&from), Some(&to)) =
+                (keys.get(&connection.from.entity), keys.get(&connection.to.entity))
+            else {
+                continue;
+            };
+
+            connection.from.entity = from;
+            connection.to.entity = to;
+
+            for point in connection.points.iter_mut() {
+                *point = (point.0 + offset, point.1 + offset);
+            }
+
+            connection.data = ConnectionItemData::new(
+                &connection.points,
+                project.entities[from].get_rect(),
+                project.entities[to].get_rect(),
+            );
+
+            let relation = connection.relation;
+            let key = project.connect(connection);
+
+            match relation {
+                RelationType::Generalization => project.entities[from].parent = Some(key),
+                RelationType::Realization => project.entities[from].implements.push(key),
+                _ => {}
+            }
+        }
+
+        Some(pasted)
+    }
+}
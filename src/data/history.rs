@@ -0,0 +1,177 @@
+use super::Project;
+use crate::elements::workspace::connection::ConnectionItemData;
+use crate::elements::workspace::entity::EntityItemData;
+use std::collections::VecDeque;
+use taffy::NodeId;
+
+/// Undo / redo history of the project.
+///
+/// Every transaction is stored as a serialized snapshot of the project taken right after it was committed.
+/// Restoring a snapshot keeps the rendering data of the entities that still exist, so undoing animates smoothly.
+#[derive(Default)]
+pub struct History {
+    undo: VecDeque<Vec<u8>>,
+    redo: Vec<Vec<u8>>,
+
+    /// Snapshot of the last committed state
+    current: Vec<u8>,
+    /// Snapshot of the state that was last saved (or loaded), `None` if it was never saved
+    saved: Option<Vec<u8>>,
+
+    /// The element that made the last commit, if it asked for consecutive changes to be merged
+    merge: Option<NodeId>,
+    /// Merge hint for the next commit
+    pending_merge: Option<NodeId>,
+
+    /// Incremented every time the committed state changes
+    revision: usize,
+}
+
+impl History {
+    /// Maximum number of undo steps
+    const LIMIT: usize = 100;
+
+    #[inline]
+    fn snapshot(project: &Project) -> Vec<u8> {
+        postcard::to_stdvec(project).unwrap_or_default()
+    }
+
+    /// Clear the history and use the project as the new starting point
+    pub fn reset(&mut self, project: &Project) {
+        self.undo.clear();
+        self.redo.clear();
+        self.current = Self::snapshot(project);
+        self.saved = Some(self.current.clone());
+        self.merge = None;
+        self.pending_merge = None;
+        self.revision += 1;
+    }
+
+    /// Remember the last committed state as the saved one
+    pub fn mark_saved(&mut self) {
+        self.saved = Some(self.current.clone());
+    }
+
+    /// Treat the project as modified, even if it wasn't changed (e.g. after restoring it from an autosave)
+    pub fn mark_unsaved(&mut self) {
+        self.saved = None;
+    }
+
+    /// Whether the project changed since it was last saved
+    #[inline]
+    pub fn is_modified(&self) -> bool {
+        self.saved.as_ref() != Some(&self.current)
+    }
+
+    /// Changes every time the committed state does (used for updating data derived from the project)
+    #[inline]
+    pub fn revision(&self) -> usize {
+        self.revision
+    }
+
+    /// Merge the next commit with the previous one, if both were made by the same element.
+    ///
+    /// Used for merging consecutive keystrokes in text inputs.
+    pub fn merge_with(&mut self, node: NodeId) {
+        self.pending_merge = Some(node);
+    }
+
+    /// Prevent the next commit from being merged with the previous one
+    pub fn seal(&mut self) {
+        self.merge = None;
+    }
+
+    /// Record a new transaction if the project changed since the last commit.
+    ///
+    /// Returns true if a change was recorded.
+    pub fn commit(&mut self, project: &Project) -> bool {
+        let merge = self.pending_merge.take();
+        let snapshot = Self::snapshot(project);
+
+        if snapshot == self.current {
+            return false;
+        }
+
+        let previous = std::mem::replace(&mut self.current, snapshot);
+        self.redo.clear();
+
+        if merge.is_none() || merge != self.merge {
+            self.undo.push_back(previous);
+
+            if self.undo.len() > Self::LIMIT {
+                self.undo.pop_front();
+            }
+        }
+
+        self.merge = merge;
+        self.revision += 1;
+
+        true
+    }
+
+    /// Revert the project to the previous transaction.
+    ///
+    /// Returns true if the project was modified.
+    pub fn undo(&mut self, project: &mut Project) -> bool {
+        // Don't lose changes that weren't committed yet
+        self.commit(project);
+
+        let Some(snapshot) = self.undo.pop_back() else {
+            return false;
+        };
+
+        let current = std::mem::replace(&mut self.current, snapshot);
+        self.redo.push(current);
+        self.merge = None;
+        self.revision += 1;
+
+        Self::restore(project, &self.current)
+    }
+
+    /// Reapply the last undone transaction.
+    ///
+    /// Returns true if the project was modified.
+    pub fn redo(&mut self, project: &mut Project) -> bool {
+        let Some(snapshot) = self.redo.pop() else {
+            return false;
+        };
+
+        let current = std::mem::replace(&mut self.current, snapshot);
+        self.undo.push_back(current);
+        self.merge = None;
+        self.revision += 1;
+
+        Self::restore(project, &self.current)
+    }
+
+    fn restore(project: &mut Project, snapshot: &[u8]) -> bool {
+        let Ok(mut restored) = postcard::from_bytes::<Project>(snapshot) else {
+            return false;
+        };
+
+        // Keep the rendering data of the existing entities so they animate to their restored positions
+        for (key, entity) in restored.entities.iter_mut() {
+            entity.data = match project.entities.get_mut(key) {
+                Some(old) => std::mem::take(&mut old.data),
+                None => EntityItemData::new(entity.position),
+            };
+        }
+
+        // Paths depend on the explicit points, so they have to be recomputed
+        for (_, connection) in restored.connections.iter_mut() {
+            let (Some(start), Some(end)) = (
+                restored.entities.get(connection.from.entity),
+                restored.entities.get(connection.to.entity),
+            ) else {
+                continue;
+            };
+
+            connection.data =
+                ConnectionItemData::new(&connection.points, start.get_rect(), end.get_rect());
+        }
+
+        *project = restored;
+
+        true
+    }
+}
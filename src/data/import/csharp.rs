@@ -0,0 +1,816 @@
+use super::{arrange, inherit};
+use crate::data::{
+    entity::{
+        AccessModifier, Direction, EntityType, Field, FieldKind, Inheritance, Literal, Method,
+        MethodKind, Mutability, Parameter, TypeParameter,
+    },
+    project::{EntityKey, Project},
+    Entity,
+};
+
+/// Keywords which can precede a type or member declaration
+const MODIFIERS: &[&str] = &[
+    "public", "private", "protected", "internal", "static", "abstract", "virtual", "override",
+    "sealed", "readonly", "const", "new", "partial", "async", "extern", "unsafe", "volatile",
+    "required", "file",
+];
+
+/// Keywords starting a type declaration
+const TYPES: &[&str] = &["class", "interface", "struct", "record", "enum"];
+
+/// A type declared in the source, before it's added to the project
+struct Declaration {
+    entity: Entity,
+
+    /// The names of the base types, with their type arguments
+    bases: Vec<(String, Vec<String>)>,
+}
+
+/// Very lenient C# parser.
+///
+/// It only understands the declarations, method bodies and initializers are skipped.
+struct Parser {
+    tokens: Vec<String>,
+    pos: usize,
+
+    namespace: Option<String>,
+    declarations: Vec<Declaration>,
+}
+
+/// Creates a project from C# source files.
+///
+/// The project name is taken from the first namespace, or the fallback if there's none.
+pub fn import<'a>(fallback: &str, sources: impl IntoIterator<Item = &'a str>) -> Project {
+    let mut namespace = None;
+    let mut declarations = Vec::new();
+
+    for source in sources {
+        let mut parser = Parser::new(source);
+        parser.parse_scope(false);
+
+        namespace = namespace.or(parser.namespace);
+        declarations.append(&mut parser.declarations);
+    }
+
+    let mut project = Project::new(namespace.unwrap_or_else(|| fallback.to_string()));
+    let mut keys = Vec::new();
+
+    for Declaration { entity, bases } in declarations {
+        keys.push((project.add_entity(entity), bases));
+    }
+
+    let find = |project: &Project, name: &str| -> Option<EntityKey> {
+        // Namespaces are ignored
+        let name = name.rsplit('.').next().unwrap_or_default();
+
+        project
+            .entities
+            .iter()
+            .find(|(_, e)| e.name == name)
+            .map(|(key, _)| key)
+    };
+
+    for (key, bases) in keys.iter() {
+        for (base, arguments) in bases {
+            if let Some(base) = find(&project, base) {
+                inherit(&mut project, *key, base, arguments.clone());
+            }
+        }
+    }
+
+    let keys = keys.into_iter().map(|(key, _)| key).collect::<Vec<_>>();
+    arrange(&mut project, &keys);
+
+    project
+}
+
+fn tokenize(source: &str) -> Vec<String> {
+    let chars = source.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let mut line_start = true;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c == '\n' {
+            line_start = true;
+            i += 1;
+            continue;
+        }
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        // Preprocessor directives
+        if c == '#' && line_start {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+
+            continue;
+        }
+
+        line_start = false;
+
+        // Comments
+        if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+
+            continue;
+        }
+
+        if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+
+            i += 2;
+            continue;
+        }
+
+        // String and char literals (kept whole for the default values of parameters)
+        if c == '"' || c == '\'' || ((c == '@' || c == '$') && chars.get(i + 1) == Some(&'"')) {
+            let start = i;
+            let verbatim = c == '@' || (c == '$' && chars.get(i + 2) == Some(&'@'));
+            while chars[i] != '"' && chars[i] != '\'' {
+                i += 1;
+            }
+
+            let quote = chars[i];
+            i += 1;
+
+            while i < chars.len() && chars[i] != quote {
+                if chars[i] == '\\' && !verbatim {
+                    i += 1;
+                }
+
+                i += 1;
+            }
+
+            i += 1;
+            tokens.push(chars[start..i.min(chars.len())].iter().collect());
+            continue;
+        }
+
+        // Identifiers, keywords and numbers
+        if c.is_alphanumeric() || c == '_' || c == '@' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '@')
+            {
+                i += 1;
+            }
+
+            tokens.push(chars[start..i].iter().collect::<String>().replace('@', ""));
+            continue;
+        }
+
+        // Lambda / expression body arrow
+        if c == '=' && chars.get(i + 1) == Some(&'>') {
+            tokens.push("=>".to_string());
+            i += 2;
+            continue;
+        }
+
+        // Comparison operators, so they aren't mistaken for an assignment
+        if matches!(c, '=' | '!' | '<' | '>') && chars.get(i + 1) == Some(&'=') {
+            tokens.push(format!("{c}="));
+            i += 2;
+            continue;
+        }
+
+        tokens.push(c.to_string());
+        i += 1;
+    }
+
+    tokens
+}
+
+#[inline]
+fn word(token: &str) -> bool {
+    token.starts_with(|c: char| c.is_alphanumeric() || c == '_')
+}
+
+/// Joins the tokens of a type back into a string
+fn join(tokens: &[String]) -> String {
+    let mut result = String::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        if i > 0 && word(token) && (word(&tokens[i - 1]) || tokens[i - 1] == ",") {
+            result.push(' ');
+        }
+
+        result.push_str(token);
+    }
+
+    result
+}
+
+/// Splits the tokens by commas, which aren't nested in any brackets
+fn split(tokens: &[String]) -> Vec<&[String]> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (i, token) in tokens.iter().enumerate() {
+        match token.as_str() {
+            "(" | "[" | "<" | "{" => depth += 1,
+            ")" | "]" | ">" | "}" => depth -= 1,
+            "," if depth == 0 => {
+                parts.push(&tokens[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    parts.push(&tokens[start..]);
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+/// Splits a type into its name and type arguments (e.g. `Repository<User>`)
+fn generic(tokens: &[String]) -> (String, Vec<String>) {
+    match tokens.iter().position(|t| t == "<") {
+        Some(open) if tokens.last().is_some_and(|t| t == ">") => (
+            join(&tokens[..open]),
+            split(&tokens[open + 1..tokens.len() - 1])
+                .into_iter()
+                .map(join)
+                .collect(),
+        ),
+        _ => (join(tokens), Vec::new()),
+    }
+}
+
+/// Parses the type parameters of a declaration, the tokens between the angle brackets (e.g. `in T, U`)
+fn type_parameters(tokens: &[String]) -> Vec<TypeParameter> {
+    split(tokens)
+        .into_iter()
+        .filter_map(|param| {
+            // The variance (`in` / `out`) is dropped
+            Some(TypeParameter {
+                name: param.last()?.clone(),
+                constraints: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+/// Adds the constraints from the `where` clauses (e.g. `where T : IEntity, new()`) to the parameters
+fn constraints(tokens: &[String], parameters: &mut [TypeParameter]) {
+    let mut clauses = tokens.split(|t| t == "where").skip(1);
+
+    while let Some([name, colon, rest @ ..]) = clauses.next() {
+        if colon != ":" {
+            continue;
+        }
+
+        if let Some(parameter) = parameters.iter_mut().find(|p| p.name == *name) {
+            parameter.constraints = split(rest).into_iter().map(join).collect();
+        }
+    }
+}
+
+/// Finds the closing angle bracket matching the one at the start of the tokens
+fn close_angle(tokens: &[String]) -> Option<usize> {
+    let mut depth = 0;
+
+    tokens.iter().position(|t| {
+        match t.as_str() {
+            "<" => depth += 1,
+            ">" => depth -= 1,
+            _ => {}
+        }
+
+        depth == 0
+    })
+}
+
+fn access_modifier(modifiers: &[&str], default: AccessModifier) -> AccessModifier {
+    if modifiers.contains(&"protected") {
+        AccessModifier::Protected
+    } else if modifiers.contains(&"private") {
+        AccessModifier::Private
+    } else if modifiers.contains(&"public") || modifiers.contains(&"internal") {
+        AccessModifier::Public
+    } else {
+        default
+    }
+}
+
+impl Parser {
+    fn new(source: &str) -> Self {
+        Self {
+            tokens: tokenize(source),
+            pos: 0,
+
+            namespace: None,
+            declarations: Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<&str> {
+        self.pos += 1;
+        self.tokens.get(self.pos - 1).map(String::as_str)
+    }
+
+    /// Skips a balanced group of brackets, starting at the opening one
+    fn skip_group(&mut self) {
+        let mut depth = 0;
+
+        while let Some(token) = self.next() {
+            match token {
+                "{" | "(" | "[" => depth += 1,
+                "}" | ")" | "]" => depth -= 1,
+                _ => {}
+            }
+
+            if depth <= 0 {
+                break;
+            }
+        }
+    }
+
+    /// Skips everything until the end of the statement (the semicolon is consumed)
+    fn skip_statement(&mut self) {
+        while let Some(token) = self.peek() {
+            match token {
+                ";" => {
+                    self.pos += 1;
+                    break;
+                }
+                "{" | "(" | "[" => self.skip_group(),
+                "}" => break,
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    /// Collects the tokens of a declaration header.
+    ///
+    /// Stops before a `{`, `;`, `=>` or `=` which isn't nested in brackets.
+    fn header(&mut self) -> Vec<String> {
+        let mut header = Vec::new();
+        let mut depth = 0;
+
+        while let Some(token) = self.peek() {
+            match token {
+                "(" | "[" => depth += 1,
+                ")" | "]" => depth -= 1,
+                "{" | ";" | "=>" | "=" | "}" if depth == 0 => break,
+                _ => {}
+            }
+
+            header.push(token.to_string());
+            self.pos += 1;
+        }
+
+        header
+    }
+
+    /// Parses a namespace, or the whole file
+    fn parse_scope(&mut self, nested: bool) {
+        while let Some(token) = self.peek() {
+            match token {
+                "}" => {
+                    self.pos += 1;
+                    if nested {
+                        return;
+                    }
+                }
+                "[" => self.skip_group(),
+                ";" => self.pos += 1,
+                "using" => self.skip_statement(),
+                "namespace" => {
+                    self.pos += 1;
+                    let name = self.header();
+                    self.namespace.get_or_insert(join(&name));
+
+                    if self.next() == Some("{") {
+                        self.parse_scope(true);
+                    }
+                }
+                _ => self.parse_declaration(None),
+            }
+        }
+    }
+
+    /// Parses a type or member declaration
+    fn parse_declaration(&mut self, parent: Option<usize>) {
+        let start = self.pos;
+        let header = self.header();
+
+        let kind = header
+            .iter()
+            .take_while(|t| *t != "(")
+            .position(|t| TYPES.contains(&t.as_str()));
+
+        match kind {
+            Some(kind) => {
+                self.pos = start;
+                self.parse_type(&header, kind);
+            }
+            None if parent.is_some() => self.parse_member(header, parent.unwrap()),
+            None => match self.peek() {
+                Some("{") => self.skip_group(),
+                _ => self.skip_statement(),
+            },
+        }
+    }
+
+    fn parse_type(&mut self, header: &[String], kind: usize) {
+        self.pos += header.len();
+
+        let modifiers = header[..kind].iter().map(String::as_str).collect::<Vec<_>>();
+        let keyword = header[kind].as_str();
+
+        let Some(name) = header.get(kind + 1) else {
+            match self.peek() {
+                Some("{") => self.skip_group(),
+                _ => self.skip_statement(),
+            }
+
+            return;
+        };
+
+        // The base of an enum is its underlying type, so it's ignored
+        if keyword == "enum" {
+            let mut entity = Entity::new(name.clone(), EntityType::Enum, (0, 0));
+
+            if self.peek() == Some("{") {
+                entity.literals = self.parse_literals();
+            }
+
+            self.declarations.push(Declaration {
+                entity,
+                bases: Vec::new(),
+            });
+
+            return;
+        }
+
+        let entity_type = if keyword == "interface" {
+            EntityType::Interface
+        } else if modifiers.contains(&"abstract") {
+            EntityType::AbstractClass
+        } else if modifiers.contains(&"sealed") {
+            EntityType::SealedClass
+        } else {
+            EntityType::Class
+        };
+
+        let mut entity = Entity::new(name.clone(), entity_type, (0, 0));
+        let where_clause = header
+            .iter()
+            .position(|t| t == "where")
+            .unwrap_or(header.len());
+
+        // Type parameters follow the name (`class Repository<T> where T : IEntity`)
+        if header.get(kind + 2).is_some_and(|t| t == "<") {
+            if let Some(close) = close_angle(&header[kind + 2..]) {
+                entity.generics = type_parameters(&header[kind + 3..kind + 2 + close]);
+            }
+
+            constraints(&header[where_clause..], &mut entity.generics);
+        }
+
+        // Base types are listed after the colon, until a generic constraint
+        let bases = match header.iter().position(|t| t == ":") {
+            Some(colon) if colon < where_clause => split(&header[colon + 1..where_clause])
+                .into_iter()
+                .map(generic)
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        let index = self.declarations.len();
+        self.declarations.push(Declaration { entity, bases });
+
+        // Records can be declared without a body
+        if self.next() != Some("{") {
+            return;
+        }
+
+        while let Some(token) = self.peek() {
+            match token {
+                "}" => {
+                    self.pos += 1;
+                    return;
+                }
+                "[" => self.skip_group(),
+                ";" => self.pos += 1,
+                _ => self.parse_declaration(Some(index)),
+            }
+        }
+    }
+
+    /// Parses the body of an enum (e.g. `{ Red, Green = 2, [Obsolete] Blue }`)
+    fn parse_literals(&mut self) -> Vec<Literal> {
+        let start = self.pos + 1;
+        self.skip_group();
+        let end = self.pos.saturating_sub(1).max(start);
+
+        // Split by the commas, which aren't nested (shifts like `1 << 2` aren't brackets here)
+        let mut parts = vec![Vec::new()];
+        let mut depth = 0;
+
+        for token in &self.tokens[start..end] {
+            match token.as_str() {
+                "(" | "[" | "{" => depth += 1,
+                ")" | "]" | "}" => depth -= 1,
+                "," if depth == 0 => {
+                    parts.push(Vec::new());
+                    continue;
+                }
+                _ => {}
+            }
+
+            parts.last_mut().unwrap().push(token.clone());
+        }
+
+        parts
+            .into_iter()
+            .filter_map(|mut part| {
+                // Attributes
+                while part.first().is_some_and(|t| t == "[") {
+                    let close = part.iter().position(|t| t == "]")?;
+                    part.drain(..=close);
+                }
+
+                let name = part.first().filter(|t| word(t))?.clone();
+
+                // Only plain numbers are kept, expressions are numbered automatically
+                let value = match part.iter().position(|t| t == "=") {
+                    Some(equals) => join(&part[equals + 1..]).parse().ok(),
+                    None => None,
+                };
+
+                Some(Literal { name, value })
+            })
+            .collect()
+    }
+
+    fn parse_member(&mut self, header: Vec<String>, index: usize) {
+        let declaration = &self.declarations[index];
+        let interface = declaration.entity.entity_type == EntityType::Interface;
+        let class_name = declaration.entity.name.clone();
+
+        let default = if interface {
+            AccessModifier::Public
+        } else {
+            AccessModifier::Private
+        };
+
+        let modifiers = header
+            .iter()
+            .take_while(|t| MODIFIERS.contains(&t.as_str()))
+            .count();
+
+        let keywords = header[..modifiers]
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+
+        let modifier = access_modifier(&keywords, default);
+        let is_static = keywords.contains(&"static");
+
+        let rest = &header[modifiers..];
+        let terminator = self.peek().unwrap_or_default().to_string();
+
+        // Skip the body / initializer (keeping the accessors of a property)
+        let mut accessors = Vec::new();
+
+        match terminator.as_str() {
+            "{" => {
+                let start = self.pos + 1;
+                self.skip_group();
+
+                let mut depth = 0;
+                for token in &self.tokens[start..self.pos.saturating_sub(1).max(start)] {
+                    match token.as_str() {
+                        "{" | "(" | "[" => depth += 1,
+                        "}" | ")" | "]" => depth -= 1,
+                        "get" | "set" | "init" if depth == 0 => accessors.push(token.clone()),
+                        _ => {}
+                    }
+                }
+
+                // Property initializer
+                if self.peek() == Some("=") {
+                    self.skip_statement();
+                }
+            }
+            "}" => {}
+            _ => self.skip_statement(),
+        }
+
+        // Events, operators, indexers, delegates and destructors don't have a UML counterpart
+        // (only the part before the parameters is checked, constructors can call `this(...)`)
+        if rest.is_empty()
+            || rest
+                .iter()
+                .take_while(|t| *t != "(")
+                .any(|t| matches!(t.as_str(), "event" | "operator" | "this" | "delegate" | "~"))
+        {
+            return;
+        }
+
+        let entity = &mut self.declarations[index].entity;
+
+        // Methods and constructors (the parameter list follows the name, unlike a tuple type)
+        let open = rest
+            .iter()
+            .enumerate()
+            .position(|(i, t)| t == "(" && i > 0 && (word(&rest[i - 1]) || rest[i - 1] == ">"));
+
+        if let Some(open) = open {
+            // Skip type parameters (`Foo<T>(...)`)
+            let mut name = open.saturating_sub(1);
+            if rest.get(name).is_some_and(|t| t == ">") {
+                let mut depth = 0;
+                while name > 0 {
+                    match rest[name].as_str() {
+                        ">" => depth += 1,
+                        "<" => depth -= 1,
+                        _ => {}
+                    }
+
+                    name -= 1;
+
+                    if depth == 0 {
+                        break;
+                    }
+                }
+            }
+
+            let mut generics = match rest.get(name + 1) {
+                Some(t) if t == "<" && name + 1 < open => {
+                    type_parameters(&rest[name + 2..open - 1])
+                }
+                _ => Vec::new(),
+            };
+
+            let return_type = join(&rest[..name]);
+            let Some(name) = rest.get(name).filter(|t| *t != "(") else {
+                return;
+            };
+
+            // Static constructors can't be called
+            let constructor = return_type.is_empty() && *name == class_name;
+            if constructor && is_static {
+                return;
+            }
+
+            // Find the closing parenthesis
+            let mut depth = 0;
+            let close = rest[open..]
+                .iter()
+                .position(|t| {
+                    match t.as_str() {
+                        "(" => depth += 1,
+                        ")" => depth -= 1,
+                        _ => {}
+                    }
+
+                    depth == 0
+                })
+                .map(|i| open + i)
+                .unwrap_or(rest.len());
+
+            constraints(&rest[close.min(rest.len())..], &mut generics);
+
+            let arguments = split(&rest[(open + 1).min(close)..close])
+                .into_iter()
+                .map(|param| {
+                    // Attributes
+                    let param = match param.iter().rposition(|t| t == "]") {
+                        Some(end) if param[0] == "[" => &param[end + 1..],
+                        _ => param,
+                    };
+
+                    // Default value
+                    let (param, default) = match param.iter().position(|t| t == "=") {
+                        Some(eq) => (&param[..eq], Some(join(&param[eq + 1..]))),
+                        None => (param, None),
+                    };
+
+                    // Extension methods and the passing modifiers (`scoped` and `in` are passed by value)
+                    let mut direction = Direction::In;
+                    let mut param = param;
+
+                    while let Some((first, rest)) = param.split_first() {
+                        direction = match first.as_str() {
+                            "ref" => Direction::Ref,
+                            "out" => Direction::Out,
+                            "params" => Direction::Params,
+                            "this" | "in" | "scoped" | "readonly" => direction,
+                            _ => break,
+                        };
+
+                        param = rest;
+                    }
+
+                    // The last token is the name (unless there's only the type)
+                    let (name, r#type) = match param.split_last() {
+                        Some((name, ty)) if word(name) && !ty.is_empty() => {
+                            (name.clone(), join(ty))
+                        }
+                        _ => (String::new(), join(param)),
+                    };
+
+                    Parameter {
+                        name,
+                        r#type,
+                        default,
+                        direction,
+                    }
+                })
+                .collect();
+
+            if constructor {
+                entity.methods.push(Method {
+                    modifier,
+                    ..Method::constructor(name, arguments)
+                });
+
+                return;
+            }
+
+            entity.methods.push(Method {
+                name: name.clone(),
+                modifier,
+                return_type,
+                arguments,
+                generics,
+                is_static,
+                inheritance: if keywords.contains(&"abstract") {
+                    Inheritance::Abstract
+                } else if keywords.contains(&"virtual") {
+                    Inheritance::Virtual
+                } else if keywords.contains(&"override") {
+                    Inheritance::Override
+                } else {
+                    Inheritance::None
+                },
+                kind: MethodKind::Method,
+            });
+
+            return;
+        }
+
+        // Fields and properties (`Type name` or `Type name, other`)
+        let declarators = split(rest);
+        let Some((first, others)) = declarators.split_first() else {
+            return;
+        };
+
+        let Some((name, ty)) = first.split_last().filter(|(_, ty)| !ty.is_empty()) else {
+            return;
+        };
+
+        let r#type = join(ty);
+        let others = others.iter().filter_map(|d| d.last());
+
+        // Properties have a body (or an expression instead of the getter)
+        let kind = match terminator.as_str() {
+            "{" | "=>" => FieldKind::Property,
+            _ => FieldKind::Field,
+        };
+
+        let mutability = if keywords.contains(&"const") {
+            Mutability::Const
+        } else if keywords.contains(&"readonly") {
+            Mutability::ReadOnly
+        } else if kind == FieldKind::Field || accessors.iter().any(|a| a == "set") {
+            Mutability::Mutable
+        } else if accessors.iter().any(|a| a == "init") {
+            Mutability::Init
+        } else {
+            Mutability::ReadOnly
+        };
+
+        for name in std::iter::once(name).chain(others) {
+            entity.fields.push(Field {
+                name: name.clone(),
+                modifier,
+                r#type: r#type.clone(),
+                is_static,
+                mutability,
+                kind,
+            });
+        }
+    }
+}
+
@@ -0,0 +1,198 @@
+use super::entity::EntityType;
+use super::file;
+use super::project::{EntityKey, Project};
+use crate::elements::workspace::entity::{measure, EntityItemData};
+use crate::elements::workspace::Workspace;
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::{Cursor, Read};
+use zip::ZipArchive;
+
+pub mod csharp;
+pub mod plantuml;
+
+type Files = Vec<(String, Vec<u8>)>; // (Path, Content)
+
+/// Maximum width of a single row of entities (in grid cells)
+const ROW_WIDTH: f64 = 48.;
+
+/// Gap between the entities (in grid cells)
+const GAP: f64 = 2.;
+
+/// Places the entities on the grid.
+///
+/// Entities are layered by their inheritance depth, so parents and interfaces end up above their children.
+pub fn arrange(project: &mut Project, entities: &[EntityKey]) {
+    fn depth(project: &Project, key: EntityKey, cache: &mut HashMap<EntityKey, usize>) -> usize {
+        if let Some(&depth) = cache.get(&key) {
+            return depth;
+        }
+
+        // Prevents infinite recursion in case of a cycle
+        cache.insert(key, 0);
+
+        let entity = &project.entities[key];
+        let depth = entity
+            .parent
+            .iter()
+            .chain(entity.implements.iter())
+            .filter_map(|conn| project.connections.get(*conn))
+            .map(|conn| depth(project, conn.to.entity, cache) + 1)
+            .max()
+            .unwrap_or_default();
+
+        cache.insert(key, depth);
+        depth
+    }
+
+    let mut cache = HashMap::new();
+    let mut layers: Vec<Vec<EntityKey>> = Vec::new();
+
+    for &key in entities {
+        let depth = depth(project, key, &mut cache);
+
+        if layers.len() <= depth {
+            layers.resize(depth + 1, Vec::new());
+        }
+
+        layers[depth].push(key);
+    }
+
+    let mut y = 0.;
+
+    for layer in layers {
+        let mut x = 0.;
+        let mut height: f64 = 0.;
+
+        for key in layer {
+            let size = measure(&project.entities[key]) / Workspace::GRID_SIZE;
+
+            // Wrap to a new row
+            if x > 0. && x + size.x > ROW_WIDTH {
+                x = 0.;
+                y += height + GAP;
+                height = 0.;
+            }
+
+            let entity = &mut project.entities[key];
+            entity.position = ((x + size.x / 2.).round() as i32, (y + size.y / 2.).round() as i32);
+            entity.data = EntityItemData::new(entity.position);
+
+            x += size.x + GAP;
+            height = height.max(size.y);
+        }
+
+        y += height + GAP * 2.;
+    }
+}
+
+/// Makes the entity inherit from (or implement) the base, with the given type arguments.
+///
+/// Only the first class is used as the parent, the same as in C#.
+/// The declared type parameters are kept, unlike when a relation is created in the editor.
+fn inherit(project: &mut Project, entity: EntityKey, base: EntityKey, arguments: Vec<String>) {
+    if entity == base {
+        return;
+    }
+
+    let generics = project.entities[entity].generics.clone();
+
+    let connection = if project.entities[base].entity_type == EntityType::Interface {
+        project
+            .implement(entity, base)
+            .then(|| project.entities[entity].implements.last().copied())
+            .flatten()
+    } else if project.entities[entity].parent.is_none() {
+        project.set_parent(entity, Some(base));
+        project.entities[entity].parent
+    } else {
+        None
+    };
+
+    project.entities[entity].generics = generics;
+
+    if let Some(connection) = connection {
+        project.connections[connection].arguments = arguments;
+    }
+}
+
+/// Loads a project file (or imports a source file) based on the extension of its name
+pub fn from_bytes(data: &[u8], name: &str) -> Result<Project, Box<dyn Error>> {
+    let (stem, extension) = name.rsplit_once('.').unwrap_or((name, ""));
+
+    Ok(match extension {
+        "json" => file::from_json(data)?,
+        "zip" => from_zip(data, stem)?,
+        "puml" | "plantuml" | "pu" => plantuml::import(stem, &String::from_utf8_lossy(data)),
+        "cs" => csharp::import(stem, [String::from_utf8_lossy(data).as_ref()]),
+        _ => file::from_bytes(data)?,
+    })
+}
+
+/// Creates a project from the C# sources in a zip archive (e.g. one created by the export)
+pub fn from_zip(data: &[u8], fallback: &str) -> Result<Project, Box<dyn Error>> {
+    let files = unzip(data)?;
+    let sources = files
+        .iter()
+        .filter(|(name, _)| name.ends_with(".cs"))
+        .map(|(_, content)| String::from_utf8_lossy(content))
+        .collect::<Vec<_>>();
+
+    if sources.is_empty() {
+        return Err("The archive doesn't contain any C# source files".into());
+    }
+
+    Ok(csharp::import(fallback, sources.iter().map(|s| s.as_ref())))
+}
+
+/// Creates a project from the C# sources in a directory (including subdirectories)
+#[cfg(not(target_arch = "wasm32"))]
+pub fn from_directory(path: &std::path::Path) -> Result<Project, Box<dyn Error>> {
+    fn walk(path: &std::path::Path, sources: &mut Vec<String>) -> std::io::Result<()> {
+        for entry in std::fs::read_dir(path)? {
+            let path = entry?.path();
+
+            if path.is_dir() {
+                // Skip the build output
+                if !path.ends_with("bin") && !path.ends_with("obj") {
+                    walk(&path, sources)?;
+                }
+            } else if path.extension().is_some_and(|ext| ext == "cs") {
+                sources.push(std::fs::read_to_string(&path)?);
+            }
+        }
+
+        Ok(())
+    }
+
+    let mut sources = Vec::new();
+    walk(path, &mut sources)?;
+
+    if sources.is_empty() {
+        return Err("The directory doesn't contain any C# source files".into());
+    }
+
+    let fallback = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+
+    Ok(csharp::import(fallback, sources.iter().map(String::as_str)))
+}
+
+/// Reads all files from a zip archive
+fn unzip(data: &[u8]) -> Result<Files, Box<dyn Error>> {
+    let mut archive = ZipArchive::new(Cursor::new(data))?;
+    let mut files = Vec::new();
+
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        if !file.is_file() {
+            continue;
+        }
+
+        let mut content = Vec::new();
+        file.read_to_end(&mut content)?;
+
+        files.push((file.name().to_string(), content));
+    }
+
+    Ok(files)
+}
@@ -0,0 +1,715 @@
+use super::{arrange, inherit};
+use crate::data::connection::{Multiplicity, Relation, RelationType};
+use crate::data::entity::{
+    AccessModifier, Entity, EntityType, Field, FieldKind, Inheritance, Literal, Method, MethodKind,
+    Mutability, Parameter, TypeParameter,
+};
+use crate::data::project::{EntityKey, Project};
+use crate::data::Connection;
+use std::collections::HashMap;
+
+/// A relation between two entities, which is created after all entities are declared
+struct Pending {
+    relation: RelationType,
+    from: (EntityKey, Multiplicity),
+    to: (EntityKey, Multiplicity),
+
+    /// Type arguments of a generic base (e.g. `User` in `extends Repository<User>`)
+    arguments: Vec<String>,
+}
+
+struct Parser {
+    project: Project,
+
+    /// Names and aliases of the declared entities
+    names: HashMap<String, EntityKey>,
+    relations: Vec<Pending>,
+}
+
+/// Creates a project from a PlantUML class diagram.
+///
+/// Supports the class declarations, members and relations (including the ones written by the export),
+/// everything else in the diagram is ignored.
+pub fn import(fallback: &str, source: &str) -> Project {
+    let mut parser = Parser {
+        project: Project::new(fallback.to_string()),
+        names: HashMap::new(),
+        relations: Vec::new(),
+    };
+
+    // The entity whose body is being parsed (None for skipped bodies, e.g. annotations)
+    let mut body: Option<Option<EntityKey>> = None;
+    let mut comment = false;
+
+    for line in source.lines() {
+        let line = line.trim();
+
+        // Block comments
+        if comment {
+            comment = !line.contains("'/");
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("/'") {
+            comment = !rest.contains("'/");
+            continue;
+        }
+
+        if line.is_empty() || line.starts_with('\'') {
+            continue;
+        }
+
+        if let Some(key) = body {
+            if line.starts_with('}') {
+                body = None;
+            } else if let Some(key) = key {
+                parser.member(key, line);
+            }
+
+            continue;
+        }
+
+        if let Some(title) = line.strip_prefix("title ") {
+            parser.project.name = title.trim().to_string();
+        } else if let Some((key, open)) = parser.declaration(line) {
+            if open {
+                body = Some(key);
+            }
+        } else if !parser.relation(line) {
+            // Members can also be added outside of the body (e.g. `Shape : +area(): double`)
+            if let Some((name, member)) = line.split_once(" : ") {
+                if let Some(&key) = parser.names.get(&normalize(name)) {
+                    parser.member(key, member);
+                }
+            }
+        }
+    }
+
+    parser.finish()
+}
+
+/// Removes the quotes, generic parameters and namespaces from a name
+fn normalize(name: &str) -> String {
+    let name = name.trim();
+
+    if let Some(quoted) = name.strip_prefix('"') {
+        return quoted.trim_end_matches('"').trim().to_string();
+    }
+
+    let name = name.split('<').next().unwrap_or_default();
+    name.rsplit(['.', ':'])
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Splits the text by whitespace, keeping quoted strings together
+fn words(text: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut rest = text.trim_start();
+
+    while !rest.is_empty() {
+        let end = if let Some(quoted) = rest.strip_prefix('"') {
+            quoted.find('"').map_or(rest.len(), |i| i + 2)
+        } else {
+            rest.find(char::is_whitespace).unwrap_or(rest.len())
+        };
+
+        words.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+
+    words
+}
+
+/// Splits the text by commas, which aren't nested in any brackets or quotes
+fn split(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    let mut quote = None;
+
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(' | '[' | '<' | '{') => depth += 1,
+            (None, ')' | ']' | '>' | '}') => depth -= 1,
+            (None, ',') if depth == 0 => {
+                parts.push(text[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    parts.push(text[start..].trim());
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+/// Removes the type parameters following the name (e.g. `Repository<T extends IEntity> extends Base`)
+fn generics(text: &str) -> (String, Vec<TypeParameter>) {
+    let Some(open) = text
+        .find('<')
+        .filter(|&open| open > 0 && !text[..open].contains(char::is_whitespace))
+    else {
+        return (text.to_string(), Vec::new());
+    };
+
+    let mut depth = 0;
+    let close = text[open..].char_indices().find_map(|(i, c)| {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            _ => {}
+        }
+
+        (depth == 0).then_some(open + i)
+    });
+
+    let Some(close) = close else {
+        return (text.to_string(), Vec::new());
+    };
+
+    (
+        format!("{}{}", &text[..open], &text[close + 1..]),
+        type_parameters(&text[open + 1..close]),
+    )
+}
+
+/// Parses the text between the angle brackets. Both `T extends A & B` and `T : A, B` are accepted.
+fn type_parameters(text: &str) -> Vec<TypeParameter> {
+    split(text)
+        .into_iter()
+        .filter_map(|p| TypeParameter::parse(&p.replace(" extends ", ":").replace('&', ",")))
+        .collect()
+}
+
+fn multiplicity(text: Option<&str>) -> Multiplicity {
+    let Some(text) = text else {
+        return Multiplicity::One;
+    };
+
+    // Whole tokens only (e.g. `0..*`, `1..n` or `many`, but not `one`)
+    let text = text.trim_matches('"').to_lowercase();
+    let many = text
+        .split(|c: char| !(c.is_alphanumeric() || c == '*'))
+        .any(|token| match token {
+            "*" | "n" | "many" => true,
+            _ => token.parse::<u32>().is_ok_and(|n| n > 1),
+        });
+
+    if many {
+        Multiplicity::Many
+    } else {
+        Multiplicity::One
+    }
+}
+
+impl Parser {
+    /// Finds an entity by its name, or creates a new class (PlantUML declares entities implicitly)
+    fn entity(&mut self, name: &str) -> EntityKey {
+        let name = normalize(name);
+
+        if let Some(&key) = self.names.get(&name) {
+            return key;
+        }
+
+        let key = self
+            .project
+            .add_entity(Entity::new(name.clone(), EntityType::Class, (0, 0)));
+
+        self.names.insert(name, key);
+        key
+    }
+
+    /// Parses a class declaration. Returns the entity and whether a body was opened.
+    fn declaration(&mut self, line: &str) -> Option<(Option<EntityKey>, bool)> {
+        const KEYWORDS: [(&str, Option<EntityType>); 10] = [
+            ("abstract class", Some(EntityType::AbstractClass)),
+            ("abstract", Some(EntityType::AbstractClass)),
+            ("class", Some(EntityType::Class)),
+            ("entity", Some(EntityType::Class)),
+            ("struct", Some(EntityType::Class)),
+            ("exception", Some(EntityType::Class)),
+            ("interface", Some(EntityType::Interface)),
+            ("protocol", Some(EntityType::Interface)),
+            ("enum", Some(EntityType::Enum)),
+            ("annotation", None),
+        ];
+
+        let (entity_type, rest) = KEYWORDS.iter().find_map(|(keyword, entity_type)| {
+            line.strip_prefix(keyword)
+                .filter(|rest| rest.starts_with(char::is_whitespace))
+                .map(|rest| (*entity_type, rest.trim()))
+        })?;
+
+        // Body
+        let (rest, open) = match rest.split_once('{') {
+            Some((rest, body)) => (rest, !body.contains('}')),
+            None => (rest, false),
+        };
+
+        let (rest, generics) = match rest.starts_with('"') {
+            true => (rest.to_string(), Vec::new()),
+            false => generics(rest),
+        };
+
+        let words = words(&rest);
+        let name = words.first()?;
+
+        let Some(mut entity_type) = entity_type else {
+            return Some((None, open));
+        };
+
+        let key = self.entity(name);
+
+        let mut bases = Vec::new();
+        let mut i = 1;
+
+        while i < words.len() {
+            match words[i] {
+                "as" if i + 1 < words.len() => {
+                    self.names.insert(normalize(words[i + 1]), key);
+                    i += 1;
+                }
+                "extends" | "implements" => {
+                    let implements = words[i] == "implements";
+
+                    // The type arguments can contain spaces and commas (e.g. `Map<K, V>, IEntity`)
+                    let mut list = Vec::new();
+                    while i + 1 < words.len()
+                        && !matches!(words[i + 1], "extends" | "implements")
+                        && !words[i + 1].starts_with("<<")
+                    {
+                        list.push(words[i + 1]);
+                        i += 1;
+                    }
+
+                    for base in split(&list.join(" ")) {
+                        bases.push((base.to_string(), implements));
+                    }
+                }
+                stereotype if stereotype.starts_with("<<") => {
+                    match stereotype.trim_matches(['<', '>']).to_lowercase().as_str() {
+                        "sealed" | "final" => entity_type = EntityType::SealedClass,
+                        "abstract" => entity_type = EntityType::AbstractClass,
+                        "interface" => entity_type = EntityType::Interface,
+                        "enum" | "enumeration" => entity_type = EntityType::Enum,
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+
+            i += 1;
+        }
+
+        self.project.entities[key].entity_type = entity_type;
+
+        // Enums can't be generic
+        if entity_type != EntityType::Enum {
+            self.project.entities[key].generics = generics;
+        }
+
+        for (base, implements) in bases {
+            let arguments = match base.split_once('<') {
+                Some((_, arguments)) => split(arguments.strip_suffix('>').unwrap_or(arguments))
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect(),
+                None => Vec::new(),
+            };
+
+            let base = self.entity(&base);
+
+            self.relations.push(Pending {
+                relation: if implements {
+                    RelationType::Realization
+                } else {
+                    RelationType::Generalization
+                },
+                from: (key, Multiplicity::One),
+                to: (base, Multiplicity::One),
+                arguments,
+            });
+        }
+
+        Some((Some(key), open))
+    }
+
+    /// Parses a field or a method (e.g. `+name: string`, `-int count`, `#area(width: int): double`)
+    fn member(&mut self, key: EntityKey, line: &str) {
+        let mut line = line.trim();
+        let mut modifier = AccessModifier::Public;
+        let mut method = None;
+        let mut is_static = false;
+        let mut is_abstract = false;
+        let mut constructor = false;
+
+        loop {
+            if let Some(rest) = line
+                .strip_prefix("<<create>>")
+                .or_else(|| line.strip_prefix("«create»"))
+            {
+                constructor = true;
+                line = rest.trim_start();
+            } else if let Some(rest) = line.strip_prefix('{') {
+                let Some((tag, rest)) = rest.split_once('}') else {
+                    return;
+                };
+
+                match tag.trim() {
+                    "method" => method = Some(true),
+                    "field" => method = Some(false),
+                    "static" | "classifier" => is_static = true,
+                    "abstract" => is_abstract = true,
+                    _ => {}
+                }
+
+                line = rest.trim_start();
+            } else if let Some(c @ ('+' | '-' | '#' | '~')) = line.chars().next() {
+                modifier = match c {
+                    '-' => AccessModifier::Private,
+                    '#' => AccessModifier::Protected,
+                    _ => AccessModifier::Public,
+                };
+
+                line = line[1..].trim_start();
+            } else {
+                break;
+            }
+        }
+
+        // The stereotype written by the export (e.g. `+Shape(int) «create»`)
+        if let Some(rest) = line.strip_suffix("«create»") {
+            constructor = true;
+            line = rest.trim_end();
+        }
+
+        if constructor {
+            method = Some(true);
+        }
+
+        // Separators (e.g. `--`, `..`, `==`)
+        if line.is_empty() || line.chars().all(|c| matches!(c, '-' | '.' | '=' | '_')) {
+            return;
+        }
+
+        let entity = &mut self.project.entities[key];
+
+        // Enums only contain the literals (e.g. `RED` or `RED = 1`)
+        if entity.entity_type == EntityType::Enum {
+            let (name, value) = match line.split_once('=') {
+                Some((name, value)) => (name, value.trim().parse().ok()),
+                None => (line, None),
+            };
+
+            entity.literals.push(Literal {
+                name: name.trim().to_string(),
+                value,
+            });
+
+            return;
+        }
+
+        match line.find('(') {
+            Some(open) if method != Some(false) => {
+                let close = line.rfind(')').filter(|&i| i > open).unwrap_or(line.len());
+
+                let head = line[..open].trim();
+
+                // Type parameters can contain spaces (e.g. `map<K, V>`), so they're removed first
+                let mut depth = 0;
+                let start = head.strip_suffix('>').and_then(|_| {
+                    head.char_indices().rev().find_map(|(i, c)| {
+                        match c {
+                            '>' => depth += 1,
+                            '<' => depth -= 1,
+                            _ => {}
+                        }
+
+                        (depth == 0).then_some(i)
+                    })
+                });
+
+                let (head, generics) = match start {
+                    Some(start) => (
+                        head[..start].trim(),
+                        type_parameters(&head[start + 1..head.len() - 1]),
+                    ),
+                    None => (head, Vec::new()),
+                };
+
+                // Either `name(args): type` or `type name(args)`
+                let (return_type, name) =
+                    head.rsplit_once(char::is_whitespace).unwrap_or(("", head));
+
+                let return_type = line
+                    .get(close + 1..)
+                    .and_then(|rest| rest.trim().strip_prefix(':'))
+                    .unwrap_or(return_type);
+
+                let arguments = split(&line[open + 1..close])
+                    .into_iter()
+                    .filter_map(|argument| match argument.contains(':') {
+                        // Either `name: type = default` or `type name`
+                        true => Parameter::parse(argument),
+                        false => Some(match argument.rsplit_once(char::is_whitespace) {
+                            Some((r#type, name)) => Parameter {
+                                name: name.trim().to_string(),
+                                r#type: r#type.trim().to_string(),
+                                ..Default::default()
+                            },
+                            None => Parameter {
+                                r#type: argument.to_string(),
+                                ..Default::default()
+                            },
+                        }),
+                    })
+                    .collect();
+
+                if constructor {
+                    entity.methods.push(Method {
+                        modifier,
+                        ..Method::constructor(name.trim(), arguments)
+                    });
+
+                    return;
+                }
+
+                entity.methods.push(Method {
+                    name: name.trim().to_string(),
+                    modifier,
+                    return_type: return_type.trim().to_string(),
+                    arguments,
+                    generics,
+                    is_static,
+                    inheritance: if is_abstract {
+                        Inheritance::Abstract
+                    } else {
+                        Inheritance::None
+                    },
+                    kind: MethodKind::Method,
+                });
+            }
+            _ => {
+                // The markers written by the export (e.g. `+id: int {readOnly}`, `+Name: string «get, set»`)
+                let accessors = line
+                    .strip_suffix('»')
+                    .and_then(|rest| rest.rsplit_once('«'))
+                    .map(|(line, accessors)| (line.trim_end(), accessors));
+
+                let (line, kind, mutability) = match accessors {
+                    Some((line, accessors)) => (
+                        line,
+                        FieldKind::Property,
+                        if accessors.contains("set") {
+                            Mutability::Mutable
+                        } else if accessors.contains("init") {
+                            Mutability::Init
+                        } else {
+                            Mutability::ReadOnly
+                        },
+                    ),
+                    None => match line.strip_suffix("{readOnly}") {
+                        Some(line) => (line.trim_end(), FieldKind::Field, Mutability::ReadOnly),
+                        None => (line, FieldKind::Field, Mutability::Mutable),
+                    },
+                };
+
+                // Either `name: type` or `type name`
+                let (name, r#type) = match line.split_once(':') {
+                    Some((name, r#type)) => (name, r#type),
+                    None => line
+                        .rsplit_once(char::is_whitespace)
+                        .map_or((line, ""), |(r#type, name)| (name, r#type)),
+                };
+
+                entity.fields.push(Field {
+                    name: name.trim().to_string(),
+                    modifier,
+                    r#type: r#type.trim().to_string(),
+                    is_static,
+                    mutability,
+                    kind,
+                });
+            }
+        }
+    }
+
+    /// Parses a relation (e.g. `Child --|> Parent`, `Whole "1" *-- "*" Part : label`)
+    fn relation(&mut self, line: &str) -> bool {
+        let mut text = String::new();
+        let mut quoted = false;
+        let mut chars = line.chars().peekable();
+
+        // Remove the label, styles (e.g. `-[#red]->`) and directions (e.g. `-up->`)
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => quoted = !quoted,
+                ':' if !quoted => break,
+                '[' if !quoted => {
+                    for c in chars.by_ref() {
+                        if c == ']' {
+                            break;
+                        }
+                    }
+
+                    continue;
+                }
+                _ => {}
+            }
+
+            text.push(c);
+        }
+
+        for direction in ["up", "down", "left", "right", "u", "d", "l", "r"] {
+            text = text
+                .replace(&format!("-{direction}-"), "--")
+                .replace(&format!(".{direction}."), "..");
+        }
+
+        let chars = text.chars().collect::<Vec<_>>();
+        let head = |c: char| matches!(c, '<' | '>' | '|' | '*');
+        let separated = |i: Option<usize>| {
+            i.and_then(|i| chars.get(i))
+                .is_none_or(|&c| c.is_whitespace() || c == '"')
+        };
+
+        // Find the start of the arrow body (outside of quotes)
+        let mut quoted = false;
+        let Some(body) = (0..chars.len()).find(|&i| {
+            if chars[i] == '"' {
+                quoted = !quoted;
+            }
+
+            !quoted
+                && matches!(chars[i], '-' | '.')
+                && (chars
+                    .get(i + 1)
+                    .is_some_and(|&c| matches!(c, '-' | '.') || head(c))
+                    || i.checked_sub(1).is_some_and(|i| head(chars[i])))
+        }) else {
+            return false;
+        };
+
+        let mut start = body;
+        while start > 0 && head(chars[start - 1]) {
+            start -= 1;
+        }
+
+        if start > 0 && chars[start - 1] == 'o' && separated(start.checked_sub(2)) {
+            start -= 1;
+        }
+
+        let mut end = body;
+        while end < chars.len() && matches!(chars[end], '-' | '.') {
+            end += 1;
+        }
+
+        while end < chars.len() && head(chars[end]) {
+            end += 1;
+        }
+
+        if chars.get(end) == Some(&'o') && separated(Some(end + 1)) {
+            end += 1;
+        }
+
+        let arrow = chars[start..end].iter().collect::<String>();
+        let left = chars[..start].iter().collect::<String>();
+        let right = chars[end..].iter().collect::<String>();
+
+        let body_start = arrow.find(['-', '.']).unwrap_or_default();
+        let body_end = arrow.rfind(['-', '.']).map_or(arrow.len(), |i| i + 1);
+        let dotted = arrow.contains('.');
+
+        let (relation, reversed) = match (&arrow[..body_start], &arrow[body_end..]) {
+            (_, "|>") if dotted => (RelationType::Realization, false),
+            (_, "|>") => (RelationType::Generalization, false),
+            ("<|", _) if dotted => (RelationType::Realization, true),
+            ("<|", _) => (RelationType::Generalization, true),
+            (_, "*") => (RelationType::Composition, false),
+            ("*", _) => (RelationType::Composition, true),
+            (_, "o") => (RelationType::Aggregation, false),
+            ("o", _) => (RelationType::Aggregation, true),
+            ("<", ">") => (RelationType::Association, false),
+            (_, ">") => (RelationType::OneWayAssociation, false),
+            ("<", _) => (RelationType::OneWayAssociation, true),
+            _ => (RelationType::Association, false),
+        };
+
+        // Sides are `Name "multiplicity"` and `"multiplicity" Name`
+        let mut left = words(&left);
+        let mut right = words(&right);
+
+        let left_multiplicity = (left.len() > 1 && left[left.len() - 1].starts_with('"'))
+            .then(|| left.pop())
+            .flatten();
+        let right_multiplicity =
+            (right.len() > 1 && right[0].starts_with('"')).then(|| right.remove(0));
+
+        let ([from], [to]) = (left.as_slice(), right.as_slice()) else {
+            return false;
+        };
+
+        let mut from = (self.entity(from), multiplicity(left_multiplicity));
+        let mut to = (self.entity(to), multiplicity(right_multiplicity));
+
+        if reversed {
+            (from, to) = (to, from);
+        }
+
+        self.relations.push(Pending {
+            relation,
+            from,
+            to,
+            arguments: Vec::new(),
+        });
+        true
+    }
+
+    fn finish(mut self) -> Project {
+        let project = &mut self.project;
+
+        for Pending {
+            relation,
+            from,
+            to,
+            arguments,
+        } in self.relations
+        {
+            let (from, from_multiplicity) = from;
+            let (to, to_multiplicity) = to;
+
+            match relation {
+                RelationType::Generalization | RelationType::Realization => {
+                    inherit(project, from, to, arguments);
+                }
+                _ => {
+                    project.connect(Connection::new(
+                        relation,
+                        Relation {
+                            entity: from,
+                            multiplicity: from_multiplicity,
+                        },
+                        Relation {
+                            entity: to,
+                            multiplicity: to_multiplicity,
+                        },
+                        vec![],
+                        project.entities[from].get_rect(),
+                        project.entities[to].get_rect(),
+                    ));
+                }
+            }
+        }
+
+        let keys = project.ordered_entities.clone();
+        arrange(project, &keys);
+
+        self.project
+    }
+}
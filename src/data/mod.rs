@@ -1,7 +1,18 @@
+pub mod codegen;
 pub mod connection;
+pub mod diagram;
 pub mod entity;
+pub mod file;
+pub mod fragment;
+pub mod history;
+pub mod import;
 pub mod project;
+pub mod reference;
+pub mod signature;
+pub mod validation;
 
 pub use connection::Connection;
 pub use entity::Entity;
+pub use fragment::Fragment;
+pub use history::History;
 pub use project::Project;
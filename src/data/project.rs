@@ -1,8 +1,9 @@
 #![allow(dead_code)]
 
 use super::{
+    codegen::{self, BaseArgument},
     connection::{Relation, RelationType},
-    entity::EntityType,
+    entity::{Direction, EntityType, Method, Mutability, TypeParameter},
     Connection, Entity,
 };
 use serde::{Deserialize, Serialize};
@@ -56,7 +57,7 @@ impl Project {
     pub fn set_parent(&mut self, entity: EntityKey, parent: Option<EntityKey>) -> bool {
         if let Some(parent) = parent {
             let ent = &self.entities[entity];
-            if ent.entity_type == EntityType::Interface {
+            if !ent.entity_type.can_inherit() {
                 return false;
             }
 
@@ -64,9 +65,8 @@ impl Project {
                 self.set_parent(entity, None);
             }
 
-            match self.entities[parent].entity_type {
-                EntityType::Interface | EntityType::SealedClass => return false,
-                _ => {}
+            if !self.entities[parent].entity_type.can_be_inherited() {
+                return false;
             }
 
             let conn = self.connect(Connection::new(
@@ -95,7 +95,9 @@ impl Project {
     }
 
     pub fn implement(&mut self, entity: EntityKey, interface: EntityKey) -> bool {
-        if self.entities[interface].entity_type != EntityType::Interface {
+        if self.entities[interface].entity_type != EntityType::Interface
+            || !self.entities[entity].entity_type.can_implement()
+        {
             return false;
         }
 
@@ -117,6 +119,47 @@ impl Project {
         true
     }
 
+    /// Sets the type arguments from a reference (e.g. `Repository<User>`), the name itself is ignored
+    pub fn set_arguments(&mut self, connection: ConnectionKey, reference: &str) {
+        let arguments = match reference.split_once('<') {
+            Some((_, arguments)) => arguments.trim_end().trim_end_matches('>'),
+            None => "",
+        };
+
+        // Only the commas which aren't nested in the arguments (e.g. `Dictionary<K, V>`)
+        let mut parts = Vec::new();
+        let mut depth = 0;
+        let mut start = 0;
+
+        for (i, c) in arguments.char_indices() {
+            match c {
+                '<' | '(' | '[' => depth += 1,
+                '>' | ')' | ']' => depth -= 1,
+                ',' if depth == 0 => {
+                    parts.push(arguments[start..i].trim().to_string());
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+
+        parts.push(arguments[start..].trim().to_string());
+        parts.retain(|part| !part.is_empty());
+
+        self.connections[connection].arguments = parts;
+    }
+
+    /// The entity the connection points to, as it's referenced in code (e.g. `Repository<User>`)
+    pub fn reference(&self, connection: ConnectionKey) -> String {
+        let connection = &self.connections[connection];
+        let name = &self.entities[connection.to.entity].name;
+
+        match connection.arguments.is_empty() {
+            true => name.clone(),
+            false => format!("{name}<{}>", connection.arguments.join(", ")),
+        }
+    }
+
     pub fn associate(&mut self, from: EntityKey, to: EntityKey) -> bool {
         let conn = self.connect(Connection::new(
             RelationType::Association,
@@ -180,6 +223,37 @@ impl Project {
         }
     }
 
+    /// Changes the type of the entity, removing the relations (and type parameters) the new type can't have
+    pub fn set_entity_type(&mut self, entity: EntityKey, entity_type: EntityType) {
+        self.entities[entity].entity_type = entity_type;
+
+        if entity_type == EntityType::Enum {
+            self.entities[entity].generics.clear();
+        }
+
+        let invalid = self.entities[entity]
+            .connections
+            .iter()
+            .copied()
+            .filter(|&key| {
+                let connection = &self.connections[key];
+                let outgoing = connection.from.entity == entity;
+
+                match connection.relation {
+                    RelationType::Generalization if outgoing => !entity_type.can_inherit(),
+                    RelationType::Generalization => !entity_type.can_be_inherited(),
+                    RelationType::Realization if outgoing => !entity_type.can_implement(),
+                    RelationType::Realization => entity_type != EntityType::Interface,
+                    _ => false,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for key in invalid {
+            self.disconnect(key);
+        }
+    }
+
     /// Modifies entity based on callback
     pub fn entity_mut(
         &mut self,
@@ -193,6 +267,87 @@ impl Project {
         false
     }
 
+    /// Checks that all the keys point to existing entities and connections (e.g. after loading a file)
+    pub fn integrity_errors(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        for (key, entity) in self.entities.iter() {
+            let name = &entity.name;
+
+            if entity.key != key {
+                errors.push(format!("Entity \"{name}\" has a mismatched key"));
+            }
+
+            match self.ordered_entities.iter().filter(|&&k| k == key).count() {
+                0 => errors.push(format!(
+                    "Entity \"{name}\" is missing from the display order"
+                )),
+                1 => {}
+                _ => errors.push(format!("Entity \"{name}\" is in the display order twice")),
+            }
+
+            for &conn in entity.connections.iter() {
+                match self.connections.get(conn) {
+                    None => errors.push(format!("Entity \"{name}\" has a missing connection")),
+                    Some(connection)
+                        if connection.from.entity != key && connection.to.entity != key =>
+                    {
+                        errors.push(format!(
+                            "Entity \"{name}\" has a connection that isn't attached to it"
+                        ))
+                    }
+                    _ => {}
+                }
+            }
+
+            let relations = entity
+                .parent
+                .iter()
+                .map(|conn| (conn, RelationType::Generalization))
+                .chain(
+                    entity
+                        .implements
+                        .iter()
+                        .map(|conn| (conn, RelationType::Realization)),
+                );
+
+            for (conn, relation) in relations {
+                if !self
+                    .connections
+                    .get(*conn)
+                    .is_some_and(|c| c.relation == relation && c.from.entity == key)
+                {
+                    errors.push(format!(
+                        "Entity \"{name}\" has an invalid {relation:?} connection"
+                    ));
+                }
+            }
+        }
+
+        for &key in self.ordered_entities.iter() {
+            if !self.entities.contains_key(key) {
+                errors.push("The display order contains a missing entity".to_string());
+            }
+        }
+
+        for (key, connection) in self.connections.iter() {
+            for end in [&connection.from, &connection.to] {
+                if !self
+                    .entities
+                    .get(end.entity)
+                    .is_some_and(|e| e.connections.contains(&key))
+                {
+                    errors.push(format!(
+                        "A {:?} connection points to a missing entity",
+                        connection.relation
+                    ));
+                }
+            }
+        }
+
+        errors
+    }
+
     pub fn get_entity_connections(&self, entity: EntityKey) -> Vec<ConnectionKey> {
         self.entities[entity].connections.iter().copied().collect()
     }
@@ -209,18 +364,41 @@ impl Project {
         let entity = &self.entities[entity];
         tokens.push(Token::Keyword(Keyword::Public));
         tokens.append(entity.entity_type.as_token().as_mut());
-        tokens.push(Token::Identifier(entity.name.clone()));
+        tokens.push(Token::Identifier(
+            entity.name.clone() + &TypeParameter::list(&entity.generics),
+        ));
 
+        // Enums only contain the literals (and can't inherit or implement anything)
+        if entity.entity_type == EntityType::Enum {
+            let count = entity.literals.len();
+
+            tokens.push(Token::Block(
+                entity
+                    .literals
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, literal)| {
+                        let separator = (i + 1 < count).then_some(Token::Comma);
+
+                        std::iter::once(Token::NewLine)
+                            .chain(literal.as_token())
+                            .chain(separator)
+                    })
+                    .collect(),
+            ));
+
+            return tokens;
+        }
+
+        let interface = entity.entity_type == EntityType::Interface;
         let mut implements = Vec::new();
 
         if let Some(parent) = entity.parent {
-            let parent = &self.entities[self.connections[parent].to.entity];
-            implements.push(parent.name.clone());
+            implements.push(self.reference(parent));
         }
 
         for &connection in entity.implements.iter() {
-            let interface = &self.entities[self.connections[connection].to.entity];
-            implements.push(interface.name.clone());
+            implements.push(self.reference(connection));
         }
 
         if !implements.is_empty() {
@@ -229,22 +407,69 @@ impl Project {
             ));
         }
 
+        tokens.append(&mut entity.generics.as_token());
+
         tokens.push(Token::Block(
             entity
                 .fields
                 .iter()
                 .flat_map(|field| std::iter::once(Token::NewLine).chain(field.as_token()))
-                .chain(
-                    entity.methods.iter().flat_map(|method| {
-                        std::iter::once(Token::NewLine).chain(method.as_token())
-                    }),
-                )
+                .chain(entity.methods.iter().flat_map(|method| {
+                    // Interfaces can't be instantiated
+                    if interface && method.is_constructor() {
+                        return vec![];
+                    }
+
+                    // Interface methods are only declared (unless they're static)
+                    let mut tokens = if interface && !method.is_static {
+                        [method.signature(), vec![Token::SemiColon]].concat()
+                    } else {
+                        method.as_token()
+                    };
+
+                    // Goes between the parameters and the body
+                    if let Some(call) = self.base_call(entity, method) {
+                        tokens.insert(tokens.len() - 1, call);
+                    }
+
+                    [vec![Token::NewLine], tokens].concat()
+                }))
                 .collect::<Vec<_>>(),
         ));
 
         tokens
     }
 
+    /// The call of the parent's constructor (e.g. `: base(name)`), unless the implicit `base()` is enough
+    fn base_call(&self, entity: &Entity, constructor: &Method) -> Option<Token> {
+        if !constructor.is_constructor() {
+            return None;
+        }
+
+        let arguments = codegen::base_arguments(self, entity, constructor)?;
+        if arguments.is_empty() {
+            return None;
+        }
+
+        let arguments = arguments.iter().map(|argument| match *argument {
+            BaseArgument::Forward(parameter, i) => match parameter.direction {
+                Direction::Ref | Direction::Out => {
+                    let keyword = parameter.direction.keyword().unwrap_or_default();
+                    format!("{keyword} {}", parameter.name_or_index(i))
+                }
+                _ => parameter.name_or_index(i),
+            },
+            BaseArgument::Missing(parameter, i) => {
+                format!("default /* TODO: {} */", parameter.name_or_index(i))
+            }
+        });
+
+        let arguments = arguments.collect::<Vec<_>>().join(", ");
+        Some(Token::Implementation(vec![Token::Identifier(format!(
+            "base({arguments})"
+        ))]))
+    }
+
     pub fn sanitize(&self, name: &str) -> String {
         sanitize_filename::sanitize_with_options(
             name,
@@ -278,7 +503,16 @@ pub enum Keyword {
     Interface,
     Abstract,
     Sealed,
+    Enum,
     Namespace,
+    Static,
+    Virtual,
+    Override,
+    Readonly,
+    Const,
+    Ref,
+    Out,
+    Params,
 }
 
 #[derive(Clone)]
@@ -286,12 +520,13 @@ pub enum Token {
     Keyword(Keyword),
     Identifier(String),
     Block(Vec<Token>),
-    Accessors,
-    MethodArguments(Vec<(Token, Token)>),
+    Accessors(Mutability),
+    MethodArguments(Vec<Vec<Token>>),
     Placeholder,
     NewLine,
     Space,
     SemiColon,
+    Comma,
     Implementation(Vec<Token>),
 }
 
@@ -320,7 +555,16 @@ impl Display for Token {
                     Keyword::Interface => "interface",
                     Keyword::Abstract => "abstract",
                     Keyword::Sealed => "sealed",
+                    Keyword::Enum => "enum",
                     Keyword::Namespace => "namespace",
+                    Keyword::Static => "static",
+                    Keyword::Virtual => "virtual",
+                    Keyword::Override => "override",
+                    Keyword::Readonly => "readonly",
+                    Keyword::Const => "const",
+                    Keyword::Ref => "ref",
+                    Keyword::Out => "out",
+                    Keyword::Params => "params",
                 }
             ),
             Token::Identifier(ident) => write!(f, "{}", ident.trim()),
@@ -339,11 +583,18 @@ impl Display for Token {
                 )?;
                 write!(f, "\n}}")
             }
-            Token::Accessors => write!(f, " {{ get; set; }}"),
+            Token::Accessors(mutability) => match mutability {
+                Mutability::Mutable => write!(f, " {{ get; set; }}"),
+                Mutability::Init => write!(f, " {{ get; init; }}"),
+                Mutability::ReadOnly | Mutability::Const => write!(f, " {{ get; }}"),
+            },
             Token::MethodArguments(args) => {
                 write!(f, "(")?;
-                for (i, (r#type, name)) in args.iter().enumerate() {
-                    write!(f, "{} {}", r#type, name)?;
+                for (i, arg) in args.iter().enumerate() {
+                    for token in arg {
+                        write!(f, "{}", token)?;
+                    }
+
                     if i < args.len() - 1 {
                         write!(f, ", ")?;
                     }
@@ -364,6 +615,7 @@ impl Display for Token {
                 Ok(())
             }
             Token::SemiColon => write!(f, ";"),
+            Token::Comma => write!(f, ","),
         }
     }
 }
@@ -0,0 +1,162 @@
+//! References to the entities in the member types (e.g. `User` in `List<User>`).
+//!
+//! The types are stored as they're written, the references are resolved by the names of the entities.
+
+use super::{
+    connection::{Multiplicity, Relation, RelationType},
+    project::{ConnectionKey, EntityKey},
+    Connection, Project,
+};
+
+/// The names used in a type with their byte offsets, without the qualified ones (e.g. `System.Guid`)
+pub fn names(r#type: &str) -> impl Iterator<Item = (usize, &str)> {
+    r#type
+        .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+        .filter(|name| name.starts_with(|c: char| c.is_alphabetic() || c == '_'))
+        .filter(|name| !name.contains('.'))
+        .map(move |name| (name.as_ptr() as usize - r#type.as_ptr() as usize, name))
+}
+
+/// Replaces a name in a type, only where it's used as a whole (e.g. not in `UserId` when renaming `User`)
+pub fn replace(r#type: &str, from: &str, to: &str) -> String {
+    let mut result = String::with_capacity(r#type.len());
+    let mut end = 0;
+
+    for (start, name) in names(r#type).filter(|(_, name)| *name == from) {
+        result.push_str(&r#type[end..start]);
+        result.push_str(to);
+        end = start + name.len();
+    }
+
+    result.push_str(&r#type[end..]);
+    result
+}
+
+impl Project {
+    /// Finds an entity by its name
+    pub fn find_entity(&self, name: &str) -> Option<EntityKey> {
+        self.entities
+            .iter()
+            .find(|(_, entity)| entity.name == name)
+            .map(|(key, _)| key)
+    }
+
+    /// Calls the function with every type in the project (members, constraints and type arguments)
+    fn for_each_type(&self, mut f: impl FnMut(&str)) {
+        for entity in self.entities.values() {
+            let constraints = entity.generics.iter().flat_map(|p| &p.constraints);
+            constraints.for_each(|c| f(c));
+
+            for field in &entity.fields {
+                f(&field.r#type);
+            }
+
+            for method in &entity.methods {
+                f(&method.return_type);
+
+                for parameter in &method.arguments {
+                    f(&parameter.r#type);
+                }
+
+                let constraints = method.generics.iter().flat_map(|p| &p.constraints);
+                constraints.for_each(|c| f(c));
+            }
+        }
+
+        for connection in self.connections.values() {
+            connection.arguments.iter().for_each(|a| f(a));
+        }
+    }
+
+    /// Same as [`Project::for_each_type`], but the types can be changed
+    fn for_each_type_mut(&mut self, mut f: impl FnMut(&mut String)) {
+        for entity in self.entities.values_mut() {
+            let constraints = entity.generics.iter_mut().flat_map(|p| &mut p.constraints);
+            constraints.for_each(&mut f);
+
+            for field in entity.fields.iter_mut() {
+                f(&mut field.r#type);
+            }
+
+            for method in entity.methods.iter_mut() {
+                f(&mut method.return_type);
+
+                for parameter in method.arguments.iter_mut() {
+                    f(&mut parameter.r#type);
+                }
+
+                let constraints = method.generics.iter_mut().flat_map(|p| &mut p.constraints);
+                constraints.for_each(&mut f);
+            }
+        }
+
+        for connection in self.connections.values_mut() {
+            connection.arguments.iter_mut().for_each(&mut f);
+        }
+    }
+
+    /// The number of types referencing the name
+    pub fn usages(&self, name: &str) -> usize {
+        let mut usages = 0;
+        self.for_each_type(|r#type| {
+            usages += names(r#type).filter(|(_, n)| *n == name).count().min(1);
+        });
+
+        usages
+    }
+
+    /// Replaces the name in all the types (after the entity was renamed)
+    pub fn rename_usages(&mut self, from: &str, to: &str) {
+        self.for_each_type_mut(|r#type| {
+            if names(r#type).any(|(_, name)| name == from) {
+                *r#type = replace(r#type, from, to);
+            }
+        });
+    }
+
+    /// The other entity referenced by the type of a field, if they aren't connected yet.
+    /// Returns whether the field holds many of them (e.g. `List<Item>` or `Item[]`).
+    pub fn field_reference(&self, entity: EntityKey, field: usize) -> Option<(EntityKey, bool)> {
+        let source = self.entities.get(entity)?;
+        let r#type = &source.fields.get(field)?.r#type;
+
+        let (target, name) = names(r#type).find_map(|(_, name)| {
+            let target = self.find_entity(name).filter(|&key| key != entity)?;
+            Some((target, name))
+        })?;
+
+        let connected = source.connections.iter().any(|&conn| {
+            let connection = &self.connections[conn];
+            connection.from.entity == target || connection.to.entity == target
+        });
+
+        if connected {
+            return None;
+        }
+
+        let many = r#type.trim_end_matches('?') != name;
+        Some((target, many))
+    }
+
+    /// Creates an association to the entity referenced by the type of a field
+    pub fn associate_field(&mut self, entity: EntityKey, field: usize) -> Option<ConnectionKey> {
+        let (target, many) = self.field_reference(entity, field)?;
+
+        let connection = Connection::new(
+            RelationType::Association,
+            Relation::new(entity),
+            Relation {
+                entity: target,
+                multiplicity: match many {
+                    true => Multiplicity::Many,
+                    false => Multiplicity::One,
+                },
+            },
+            vec![],
+            self.entities[entity].get_rect(),
+            self.entities[target].get_rect(),
+        );
+
+        Some(self.connect(connection))
+    }
+}
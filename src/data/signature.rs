@@ -0,0 +1,611 @@
+use super::entity::{AccessModifier, Direction, Literal, Parameter, TypeParameter};
+use std::{fmt::Display, ops::Range};
+
+/// Why a signature couldn't be parsed
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub message: String,
+
+    /// The characters (not bytes) the error refers to, empty at the end of the input
+    pub span: Range<usize>,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// A field as it's written in the sidebar (e.g. `+count: int`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldSignature {
+    /// Only present if the visibility was written out
+    pub modifier: Option<AccessModifier>,
+    pub name: String,
+    pub r#type: String,
+}
+
+/// A method as it's written in the sidebar (e.g. `find<T>(id: int): T? where T : IEntity`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodSignature {
+    /// Only present if the visibility was written out
+    pub modifier: Option<AccessModifier>,
+    pub name: String,
+    pub generics: Vec<TypeParameter>,
+    pub arguments: Vec<Parameter>,
+
+    /// Empty if the method doesn't return anything
+    pub return_type: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Identifier,
+    Literal,
+    Symbol,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: Kind,
+    text: String,
+    span: Range<usize>,
+}
+
+/// Splits the input into identifiers, literals (numbers and strings) and single character symbols
+fn tokenize(chars: &[char]) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let start = i;
+        let c = chars[i];
+        i += 1;
+
+        let kind = match c {
+            c if c.is_whitespace() => continue,
+
+            c if c.is_alphabetic() || c == '_' || c == '@' => {
+                while chars
+                    .get(i)
+                    .is_some_and(|c| c.is_alphanumeric() || *c == '_')
+                {
+                    i += 1;
+                }
+
+                Kind::Identifier
+            }
+
+            // Including suffixes and hex digits (e.g. `1.5f`, `0xFF`, `10_000`)
+            c if c.is_ascii_digit() => {
+                while let Some(&c) = chars.get(i) {
+                    let decimal = c == '.' && chars.get(i + 1).is_some_and(char::is_ascii_digit);
+                    if !(c.is_alphanumeric() || c == '_' || decimal) {
+                        break;
+                    }
+
+                    i += 1;
+                }
+
+                Kind::Literal
+            }
+
+            '"' | '\'' => {
+                loop {
+                    match chars.get(i) {
+                        None => {
+                            return Err(ParseError {
+                                message: "Unterminated string".to_string(),
+                                span: start..chars.len(),
+                            })
+                        }
+                        Some('\\') => i += 2,
+                        Some(&q) if q == c => break,
+                        _ => i += 1,
+                    }
+                }
+
+                i += 1;
+                Kind::Literal
+            }
+
+            _ => Kind::Symbol,
+        };
+
+        let end = i.min(chars.len());
+        tokens.push(Token {
+            kind,
+            text: chars[start..end].iter().collect(),
+            span: start..end,
+        });
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive descent parser of the UML notation used in the sidebar
+struct Parser {
+    chars: Vec<char>,
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(str: &str) -> Result<Self, ParseError> {
+        let chars = str.chars().collect::<Vec<_>>();
+        let tokens = tokenize(&chars)?;
+
+        Ok(Parser {
+            chars,
+            tokens,
+            pos: 0,
+        })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    /// Whether the token after the current one is the given text
+    fn peek_next_is(&self, text: &str) -> bool {
+        self.tokens
+            .get(self.pos + 1)
+            .is_some_and(|t| t.text == text)
+    }
+
+    fn is(&self, text: &str) -> bool {
+        self.peek().is_some_and(|t| t.text == text)
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        let matches = self.is(text);
+        if matches {
+            self.pos += 1;
+        }
+
+        matches
+    }
+
+    /// The span of the current token, or an empty one at the end of the input
+    fn span(&self) -> Range<usize> {
+        match self.peek() {
+            Some(token) => token.span.clone(),
+            None => self.chars.len()..self.chars.len(),
+        }
+    }
+
+    fn error(&self, message: impl Into<String>, span: Range<usize>) -> ParseError {
+        ParseError {
+            message: message.into(),
+            span,
+        }
+    }
+
+    /// An error about the current token not being the expected one
+    fn unexpected(&self, expected: &str) -> ParseError {
+        let message = match self.peek() {
+            Some(token) => format!("Expected {expected}, found `{}`", token.text),
+            None => format!("Expected {expected}"),
+        };
+
+        self.error(message, self.span())
+    }
+
+    fn expect(&mut self, text: &str) -> Result<(), ParseError> {
+        match self.eat(text) {
+            true => Ok(()),
+            false => Err(self.unexpected(&format!("`{text}`"))),
+        }
+    }
+
+    fn identifier(&mut self, what: &str) -> Result<String, ParseError> {
+        match self.peek() {
+            Some(token) if token.kind == Kind::Identifier => {
+                let name = token.text.clone();
+                self.pos += 1;
+
+                Ok(name)
+            }
+            _ => Err(self.unexpected(what)),
+        }
+    }
+
+    /// Makes sure there's nothing left
+    fn end(&self) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(token) => Err(self.error(format!("Unexpected `{}`", token.text), self.span())),
+            None => Ok(()),
+        }
+    }
+
+    /// The visibility prefix (e.g. `+` or `-`)
+    fn modifier(&mut self) -> Result<Option<AccessModifier>, ParseError> {
+        let modifier = match self.peek().map(|t| t.text.as_str()) {
+            Some("+") => AccessModifier::Public,
+            Some("-") => AccessModifier::Private,
+            Some("#") => AccessModifier::Protected,
+            Some("~") => {
+                return Err(self.error("Package visibility isn't supported", self.span()));
+            }
+            _ => return Ok(None),
+        };
+
+        self.pos += 1;
+        Ok(Some(modifier))
+    }
+
+    /// A type, returned in a normalized format (e.g. `Dictionary<string, List<int>>[]?`)
+    fn r#type(&mut self) -> Result<String, ParseError> {
+        let mut r#type = if self.is("(") {
+            let start = self.span().start;
+            self.pos += 1;
+
+            let mut elements = vec![self.r#type()?];
+            while self.eat(",") {
+                elements.push(self.r#type()?);
+            }
+
+            if !self.is(")") {
+                return Err(self.unexpected("`,` or `)`"));
+            }
+
+            let span = start..self.span().end;
+            self.pos += 1;
+
+            if elements.len() < 2 {
+                return Err(self.error("A tuple needs at least two types", span));
+            }
+
+            format!("({})", elements.join(", "))
+        } else {
+            let mut name = self.identifier("a type")?;
+            while self.eat(".") {
+                name.push('.');
+                name.push_str(&self.identifier("a type")?);
+            }
+
+            if self.eat("<") {
+                let mut arguments = vec![self.r#type()?];
+                while self.eat(",") {
+                    arguments.push(self.r#type()?);
+                }
+
+                if !self.eat(">") {
+                    return Err(self.unexpected("`,` or `>`"));
+                }
+
+                name.push_str(&format!("<{}>", arguments.join(", ")));
+            }
+
+            name
+        };
+
+        loop {
+            if self.is("?") {
+                if r#type.ends_with('?') {
+                    return Err(self.error("The type is already nullable", self.span()));
+                }
+
+                self.pos += 1;
+                r#type.push('?');
+            } else if self.eat("[") {
+                // Multidimensional arrays (e.g. `int[,]`)
+                r#type.push('[');
+                while self.eat(",") {
+                    r#type.push(',');
+                }
+
+                if !self.eat("]") {
+                    return Err(self.unexpected("`]`"));
+                }
+
+                r#type.push(']');
+            } else {
+                return Ok(r#type);
+            }
+        }
+    }
+
+    /// The type parameters of a method in angle brackets (e.g. `<T, U>`), if there are any
+    fn type_parameters(&mut self) -> Result<Vec<TypeParameter>, ParseError> {
+        let mut parameters: Vec<TypeParameter> = Vec::new();
+
+        if !self.eat("<") {
+            return Ok(parameters);
+        }
+
+        loop {
+            let span = self.span();
+            let name = self.identifier("a type parameter")?;
+
+            if parameters.iter().any(|p| p.name == name) {
+                return Err(self.error(format!("Duplicate type parameter `{name}`"), span));
+            }
+
+            parameters.push(TypeParameter {
+                name,
+                constraints: vec![],
+            });
+
+            if self.eat(">") {
+                return Ok(parameters);
+            }
+
+            if !self.eat(",") {
+                return Err(self.unexpected("`,` or `>`"));
+            }
+        }
+    }
+
+    /// The constraint clauses (e.g. `where T : IEntity, new()`) of the given type parameters
+    fn constraints(&mut self, parameters: &mut [TypeParameter]) -> Result<(), ParseError> {
+        while self.eat("where") {
+            let span = self.span();
+            let name = self.identifier("a type parameter")?;
+
+            let Some(parameter) = parameters.iter_mut().find(|p| p.name == name) else {
+                return Err(self.error(format!("`{name}` isn't a type parameter"), span));
+            };
+
+            self.expect(":")?;
+            parameter.constraints = self.constraint_list()?;
+        }
+
+        Ok(())
+    }
+
+    /// The comma separated constraints of a single type parameter (e.g. `IEntity, new()`)
+    fn constraint_list(&mut self) -> Result<Vec<String>, ParseError> {
+        let mut constraints = Vec::new();
+
+        loop {
+            if self.is("new") && self.peek_next_is("(") {
+                self.pos += 2;
+                self.expect(")")?;
+
+                constraints.push("new()".to_string());
+            } else {
+                constraints.push(self.r#type()?);
+            }
+
+            if !self.eat(",") {
+                return Ok(constraints);
+            }
+        }
+    }
+
+    /// The default value of a parameter, everything up to the next `,` or `)` outside of brackets
+    fn default(&mut self) -> Result<String, ParseError> {
+        let start = self.pos;
+        let mut depth = 0;
+
+        while let Some(token) = self.peek() {
+            match token.text.as_str() {
+                "," | ")" if depth == 0 => break,
+                "(" | "[" | "{" => depth += 1,
+                ")" | "]" | "}" => depth -= 1,
+                _ => {}
+            }
+
+            self.pos += 1;
+        }
+
+        if self.pos == start {
+            return Err(self.unexpected("a default value"));
+        }
+
+        let span = self.tokens[start].span.start..self.tokens[self.pos - 1].span.end;
+        Ok(self.chars[span].iter().collect())
+    }
+
+    /// A single parameter (e.g. `ref count: int = 0`), the name is optional
+    fn parameter(&mut self) -> Result<Parameter, ParseError> {
+        let mut direction = Direction::In;
+
+        // The keywords can be used as names too (e.g. `out: Stream`)
+        if !self.peek_next_is(":") {
+            direction = match self.peek().map(|t| t.text.as_str()) {
+                Some("ref") => Direction::Ref,
+                Some("out") => Direction::Out,
+                Some("params") => Direction::Params,
+                _ => Direction::In,
+            };
+
+            if direction != Direction::In {
+                self.pos += 1;
+            }
+        }
+
+        let mut name = String::new();
+        if self.peek().is_some_and(|t| t.kind == Kind::Identifier) && self.peek_next_is(":") {
+            name = self.identifier("a name")?;
+            self.pos += 1;
+        }
+
+        let r#type = self.r#type()?;
+
+        let default = match self.eat("=") {
+            true => Some(self.default()?),
+            false => None,
+        };
+
+        Ok(Parameter {
+            name,
+            r#type,
+            default,
+            direction,
+        })
+    }
+
+    /// The parameter list in parentheses
+    fn parameters(&mut self) -> Result<Vec<Parameter>, ParseError> {
+        let mut parameters: Vec<Parameter> = Vec::new();
+
+        self.expect("(")?;
+        if self.eat(")") {
+            return Ok(parameters);
+        }
+
+        loop {
+            let start = self.span().start;
+            let parameter = self.parameter()?;
+            let span = start..self.tokens[self.pos - 1].span.end;
+
+            if let Some(last) = parameters.last() {
+                if last.direction == Direction::Params {
+                    return Err(self.error("Only the last parameter can be `params`", span));
+                }
+
+                let required =
+                    parameter.default.is_none() && parameter.direction != Direction::Params;
+                if last.default.is_some() && required {
+                    let message = "Parameters after an optional one need a default value too";
+                    return Err(self.error(message, span));
+                }
+            }
+
+            let named = !parameter.name.is_empty();
+            if named && parameters.iter().any(|p| p.name == parameter.name) {
+                let message = format!("Duplicate parameter `{}`", parameter.name);
+                return Err(self.error(message, span));
+            }
+
+            parameters.push(parameter);
+
+            if self.eat(")") {
+                return Ok(parameters);
+            }
+
+            if !self.eat(",") {
+                return Err(self.unexpected("`,` or `)`"));
+            }
+        }
+    }
+}
+
+/// Parses a field in the format `[visibility] name: type` (e.g. `-items: List<Item>?`)
+pub fn parse_field(str: &str) -> Result<FieldSignature, ParseError> {
+    let mut parser = Parser::new(str)?;
+
+    let modifier = parser.modifier()?;
+    let name = parser.identifier("a name")?;
+
+    if !parser.eat(":") {
+        return Err(parser.unexpected("`:` followed by the type"));
+    }
+
+    let r#type = parser.r#type()?;
+    parser.end()?;
+
+    Ok(FieldSignature {
+        modifier,
+        name,
+        r#type,
+    })
+}
+
+/// Parses a type parameter of an entity in the format `Name[ : constraints]` (e.g. `T : IEntity, new()`)
+pub fn parse_type_parameter(str: &str) -> Result<TypeParameter, ParseError> {
+    let mut parser = Parser::new(str)?;
+
+    let name = parser.identifier("a name")?;
+
+    let constraints = match parser.eat(":") {
+        true => parser.constraint_list()?,
+        false => vec![],
+    };
+
+    parser.end()?;
+
+    Ok(TypeParameter { name, constraints })
+}
+
+/// Parses a literal of an enumeration in the format `Name[ = value]` (e.g. `Red = -1`)
+pub fn parse_literal(str: &str) -> Result<Literal, ParseError> {
+    let mut parser = Parser::new(str)?;
+
+    let name = parser.identifier("a name")?;
+
+    if !parser.eat("=") {
+        parser.end()?;
+        return Ok(Literal { name, value: None });
+    }
+
+    // Everything after the `=` is the value (`-` and the number are separate tokens)
+    let Some(first) = parser.peek() else {
+        return Err(parser.unexpected("a value"));
+    };
+
+    let span = first.span.start..parser.tokens[parser.tokens.len() - 1].span.end;
+    let text = parser.chars[span.clone()].iter().collect::<String>();
+
+    match text.parse() {
+        Ok(value) => Ok(Literal {
+            name,
+            value: Some(value),
+        }),
+        Err(_) => Err(parser.error(format!("`{text}` isn't a whole number"), span)),
+    }
+}
+
+/// Parses a method in the format `[visibility] name[<T>](parameters)[: type] [where T : type]`
+pub fn parse_method(str: &str) -> Result<MethodSignature, ParseError> {
+    let mut parser = Parser::new(str)?;
+
+    let modifier = parser.modifier()?;
+    let name = parser.identifier("a name")?;
+    let mut generics = parser.type_parameters()?;
+    let arguments = parser.parameters()?;
+
+    let return_type = match parser.eat(":") {
+        true => parser.r#type()?,
+        false => String::new(),
+    };
+
+    parser.constraints(&mut generics)?;
+    parser.end()?;
+
+    Ok(MethodSignature {
+        modifier,
+        name,
+        generics,
+        arguments,
+        return_type,
+    })
+}
+
+/// Parses a constructor of the given entity in the format `[visibility] Name(parameters)`
+pub fn parse_constructor(str: &str, entity: &str) -> Result<MethodSignature, ParseError> {
+    let mut parser = Parser::new(str)?;
+
+    let modifier = parser.modifier()?;
+
+    let span = parser.span();
+    let name = parser.identifier("a name")?;
+    if name != entity {
+        let message = format!("The constructor has to be named `{entity}`");
+        return Err(parser.error(message, span));
+    }
+
+    if parser.is("<") {
+        let message = "Constructors can't have type parameters";
+        return Err(parser.error(message, parser.span()));
+    }
+
+    let arguments = parser.parameters()?;
+
+    if parser.is(":") {
+        let message = "Constructors don't have a return type";
+        return Err(parser.error(message, parser.span()));
+    }
+
+    parser.end()?;
+
+    Ok(MethodSignature {
+        modifier,
+        name,
+        generics: vec![],
+        arguments,
+        return_type: String::new(),
+    })
+}
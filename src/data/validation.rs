@@ -0,0 +1,488 @@
+//! Checks the project for mistakes that would make the generated code invalid.
+//!
+//! Errors block exporting the code, warnings are only shown in the problems panel.
+
+use super::{
+    connection::RelationType,
+    entity::{EntityType, Inheritance},
+    project::{ConnectionKey, EntityKey},
+    reference, Entity, Project,
+};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// The number of problems with the severity (e.g. "1 error" or "2 errors")
+    pub fn count(self, count: usize) -> String {
+        match count {
+            1 => format!("1 {self}"),
+            _ => format!("{count} {self}s"),
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// The part of the project a diagnostic is about
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Target {
+    Entity(EntityKey),
+    Field(EntityKey, usize),
+    Method(EntityKey, usize),
+    Literal(EntityKey, usize),
+    Connection(ConnectionKey),
+}
+
+impl Target {
+    /// The entity the target belongs to (the source of a connection)
+    pub fn entity(&self, project: &Project) -> Option<EntityKey> {
+        match *self {
+            Target::Entity(entity)
+            | Target::Field(entity, _)
+            | Target::Method(entity, _)
+            | Target::Literal(entity, _) => Some(entity),
+            Target::Connection(connection) => {
+                project.connections.get(connection).map(|c| c.from.entity)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub target: Target,
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn error(target: Target, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            target,
+            message: message.into(),
+        }
+    }
+
+    fn warning(target: Target, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            target,
+            message: message.into(),
+        }
+    }
+}
+
+/// Types that don't have to be in the diagram (built-in and common library types)
+const KNOWN_TYPES: &[&str] = &[
+    "bool",
+    "byte",
+    "sbyte",
+    "char",
+    "decimal",
+    "double",
+    "float",
+    "int",
+    "uint",
+    "long",
+    "ulong",
+    "short",
+    "ushort",
+    "nint",
+    "nuint",
+    "object",
+    "string",
+    "void",
+    "dynamic",
+    "String",
+    "Object",
+    "List",
+    "Dictionary",
+    "HashSet",
+    "SortedSet",
+    "SortedDictionary",
+    "Queue",
+    "Stack",
+    "LinkedList",
+    "IEnumerable",
+    "IEnumerator",
+    "ICollection",
+    "IList",
+    "IDictionary",
+    "ISet",
+    "IReadOnlyList",
+    "IReadOnlyCollection",
+    "IReadOnlyDictionary",
+    "KeyValuePair",
+    "Tuple",
+    "ValueTuple",
+    "Nullable",
+    "Lazy",
+    "Func",
+    "Action",
+    "Predicate",
+    "Task",
+    "ValueTask",
+    "IAsyncEnumerable",
+    "DateTime",
+    "DateTimeOffset",
+    "DateOnly",
+    "TimeOnly",
+    "TimeSpan",
+    "Guid",
+    "Uri",
+    "Exception",
+    "Type",
+    "Enum",
+    "Array",
+    "Span",
+    "ReadOnlySpan",
+    "Memory",
+    "Stream",
+    "StringBuilder",
+    "CancellationToken",
+    "EventHandler",
+    "EventArgs",
+    "IDisposable",
+    "IComparable",
+    "IEquatable",
+    "Random",
+];
+
+/// Virtual methods of `object`, which can be overridden without a parent
+const OBJECT_METHODS: &[&str] = &["ToString", "Equals", "GetHashCode"];
+
+/// Keywords which can be used as generic constraints (e.g. `where T : class, new()`)
+const CONSTRAINTS: &[&str] = &["class", "struct", "new", "notnull", "unmanaged"];
+
+/// Finds the problems in the project, the errors come first
+pub fn validate(project: &Project) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    names(project, &mut diagnostics);
+
+    for (key, entity) in project.entities.iter() {
+        members(key, entity, &mut diagnostics);
+        types(project, key, entity, &mut diagnostics);
+    }
+
+    connections(project, &mut diagnostics);
+    cycles(project, &mut diagnostics);
+
+    // Stable, so the problems stay in the order of the entities
+    diagnostics.sort_by_key(|d| Reverse(d.severity));
+    diagnostics
+}
+
+/// Whether the name can be used in the code
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Entity names have to be valid and unique (every entity is exported into its own file)
+fn names(project: &Project, diagnostics: &mut Vec<Diagnostic>) {
+    let mut counts = HashMap::new();
+
+    // Case insensitive, the files would collide on some file systems
+    for entity in project.entities.values() {
+        *counts.entry(entity.name.to_lowercase()).or_insert(0) += 1;
+    }
+
+    for (key, entity) in project.entities.iter() {
+        let target = Target::Entity(key);
+        let name = &entity.name;
+
+        if name.is_empty() {
+            diagnostics.push(Diagnostic::error(target, "An entity doesn't have a name"));
+            continue;
+        }
+
+        if !is_identifier(name) {
+            diagnostics.push(Diagnostic::error(
+                target,
+                format!("`{name}` isn't a valid name"),
+            ));
+        }
+
+        if counts[&name.to_lowercase()] > 1 {
+            let message = format!("The name `{name}` is used by another entity");
+            diagnostics.push(Diagnostic::error(target, message));
+        }
+    }
+}
+
+/// Member names, types and modifiers
+fn members(key: EntityKey, entity: &Entity, diagnostics: &mut Vec<Diagnostic>) {
+    let mut declared = HashSet::new();
+
+    if entity.entity_type == EntityType::Enum {
+        for (i, literal) in entity.literals.iter().enumerate() {
+            let target = Target::Literal(key, i);
+            let name = &literal.name;
+
+            if name.is_empty() {
+                diagnostics.push(Diagnostic::error(target, "A literal doesn't have a name"));
+            } else if !is_identifier(name) {
+                let message = format!("`{name}` isn't a valid name");
+                diagnostics.push(Diagnostic::error(target, message));
+            } else if !declared.insert(name.as_str()) {
+                let message = format!("`{name}` is declared more than once");
+                diagnostics.push(Diagnostic::error(target, message));
+            }
+        }
+
+        // The fields and methods of enums aren't shown (or exported)
+        return;
+    }
+
+    for (i, field) in entity.fields.iter().enumerate() {
+        let target = Target::Field(key, i);
+        let name = &field.name;
+
+        if name.is_empty() {
+            diagnostics.push(Diagnostic::error(target, "A field doesn't have a name"));
+        } else if !is_identifier(name) {
+            let message = format!("`{name}` isn't a valid name");
+            diagnostics.push(Diagnostic::error(target, message));
+        } else if !declared.insert(name.as_str()) {
+            let message = format!("`{name}` is declared more than once");
+            diagnostics.push(Diagnostic::error(target, message));
+        }
+
+        if field.r#type.is_empty() && !name.is_empty() {
+            let message = format!("`{name}` doesn't have a type");
+            diagnostics.push(Diagnostic::error(target, message));
+        }
+    }
+
+    // Methods can be overloaded, only the same parameter types are a conflict
+    let mut signatures = HashSet::new();
+
+    for (i, method) in entity.methods.iter().enumerate() {
+        let target = Target::Method(key, i);
+        let name = &method.name;
+
+        if name.is_empty() {
+            diagnostics.push(Diagnostic::error(target, "A method doesn't have a name"));
+        } else if !is_identifier(name) {
+            let message = format!("`{name}` isn't a valid name");
+            diagnostics.push(Diagnostic::error(target, message));
+        } else if !method.is_constructor() && declared.contains(name.as_str()) {
+            let message = format!("`{name}` has the same name as a field");
+            diagnostics.push(Diagnostic::error(target, message));
+        }
+
+        let types = method.arguments.iter().map(|p| p.r#type.as_str());
+        if !signatures.insert((name, types.collect::<Vec<_>>())) && !name.is_empty() {
+            let message = format!("`{name}` is declared more than once with the same parameters");
+            diagnostics.push(Diagnostic::error(target, message));
+        }
+
+        for (i, parameter) in method.arguments.iter().enumerate() {
+            if parameter.r#type.is_empty() {
+                let parameter = parameter.name_or_index(i);
+                let message = format!("Parameter `{parameter}` of `{name}` doesn't have a type");
+                diagnostics.push(Diagnostic::error(target, message));
+            }
+        }
+
+        let message = match (method.inheritance, entity.entity_type) {
+            (Inheritance::Abstract, EntityType::AbstractClass | EntityType::Interface) => None,
+            (Inheritance::Abstract, _) => Some(format!(
+                "`{name}` is abstract, but `{}` isn't an abstract class",
+                entity.name
+            )),
+            (Inheritance::Virtual, EntityType::SealedClass) => {
+                Some(format!("`{name}` can't be virtual in a sealed class"))
+            }
+            // Every class inherits these from `object`
+            (Inheritance::Override, _) if OBJECT_METHODS.contains(&name.as_str()) => None,
+            (Inheritance::Override, _) if entity.parent.is_none() => Some(format!(
+                "`{name}` overrides a method, but `{}` doesn't have a parent",
+                entity.name
+            )),
+            _ => None,
+        };
+
+        if let Some(message) = message {
+            diagnostics.push(Diagnostic::error(target, message));
+        }
+    }
+}
+
+/// Types which aren't in the diagram (or the known types)
+fn types(project: &Project, key: EntityKey, entity: &Entity, diagnostics: &mut Vec<Diagnostic>) {
+    let entities = project
+        .entities
+        .values()
+        .map(|e| e.name.as_str())
+        .collect::<HashSet<_>>();
+
+    let generics = entity.generics.iter().map(|p| p.name.as_str());
+    let known = generics
+        .chain(KNOWN_TYPES.iter().copied())
+        .chain(entities)
+        .collect::<HashSet<_>>();
+
+    let mut check = |target: Target, r#type: &str, extra: &HashSet<&str>| {
+        let mut reported = HashSet::new();
+
+        for (_, name) in reference::names(r#type) {
+            if known.contains(name) || extra.contains(name) || !reported.insert(name) {
+                continue;
+            }
+
+            let message = format!("Unknown type `{name}`");
+            diagnostics.push(Diagnostic::warning(target, message));
+        }
+    };
+
+    let constraints = CONSTRAINTS.iter().copied().collect::<HashSet<_>>();
+    for parameter in entity.generics.iter() {
+        for constraint in parameter.constraints.iter() {
+            check(Target::Entity(key), constraint, &constraints);
+        }
+    }
+
+    if entity.entity_type == EntityType::Enum {
+        return;
+    }
+
+    for (i, field) in entity.fields.iter().enumerate() {
+        check(Target::Field(key, i), &field.r#type, &HashSet::new());
+    }
+
+    for (i, method) in entity.methods.iter().enumerate() {
+        let generics = method.generics.iter().map(|p| p.name.as_str());
+        let mut extra = generics.collect::<HashSet<_>>();
+
+        check(Target::Method(key, i), &method.return_type, &extra);
+
+        for parameter in method.arguments.iter() {
+            check(Target::Method(key, i), &parameter.r#type, &extra);
+        }
+
+        extra.extend(CONSTRAINTS);
+        for parameter in method.generics.iter() {
+            for constraint in parameter.constraints.iter() {
+                check(Target::Method(key, i), constraint, &extra);
+            }
+        }
+    }
+
+    for &conn in entity.parent.iter().chain(entity.implements.iter()) {
+        let Some(connection) = project.connections.get(conn) else {
+            continue;
+        };
+
+        for argument in connection.arguments.iter() {
+            check(Target::Connection(conn), argument, &HashSet::new());
+        }
+    }
+}
+
+/// Parents and interfaces have to be of the right type and get the right number of type arguments
+fn connections(project: &Project, diagnostics: &mut Vec<Diagnostic>) {
+    let mut parents = HashMap::new();
+
+    for (key, connection) in project.connections.iter() {
+        let target = Target::Connection(key);
+
+        let (Some(from), Some(to)) = (
+            project.entities.get(connection.from.entity),
+            project.entities.get(connection.to.entity),
+        ) else {
+            continue;
+        };
+
+        match connection.relation {
+            RelationType::Generalization => {
+                *parents.entry(connection.from.entity).or_insert(0) += 1;
+
+                if !from.entity_type.can_inherit() {
+                    let message = format!("`{}` can't have a parent", from.name);
+                    diagnostics.push(Diagnostic::error(target, message));
+                } else if !to.entity_type.can_be_inherited() {
+                    let message = format!("`{}` can't be inherited from", to.name);
+                    diagnostics.push(Diagnostic::error(target, message));
+                }
+            }
+            RelationType::Realization => {
+                if !from.entity_type.can_implement() {
+                    let message = format!("`{}` can't implement interfaces", from.name);
+                    diagnostics.push(Diagnostic::error(target, message));
+                } else if to.entity_type != EntityType::Interface {
+                    let message = format!("`{}` isn't an interface", to.name);
+                    diagnostics.push(Diagnostic::error(target, message));
+                }
+            }
+            _ => continue,
+        }
+
+        let (expected, given) = (to.generics.len(), connection.arguments.len());
+        if expected != given {
+            let message = format!(
+                "`{}` expects {expected} type argument(s), but {given} were given",
+                to.name
+            );
+
+            diagnostics.push(Diagnostic::error(target, message));
+        }
+    }
+
+    for (key, entity) in project.entities.iter() {
+        if parents.get(&key).is_some_and(|&count| count > 1) {
+            let message = format!("`{}` has more than one parent", entity.name);
+            diagnostics.push(Diagnostic::error(Target::Entity(key), message));
+        }
+    }
+}
+
+/// Entities which (indirectly) inherit from or implement themselves
+fn cycles(project: &Project, diagnostics: &mut Vec<Diagnostic>) {
+    let mut bases: HashMap<EntityKey, Vec<EntityKey>> = HashMap::new();
+
+    for connection in project.connections.values() {
+        if matches!(
+            connection.relation,
+            RelationType::Generalization | RelationType::Realization
+        ) {
+            let entry = bases.entry(connection.from.entity).or_default();
+            entry.push(connection.to.entity);
+        }
+    }
+
+    for (key, entity) in project.entities.iter() {
+        let mut visited = HashSet::new();
+        let mut stack = bases.get(&key).cloned().unwrap_or_default();
+
+        while let Some(base) = stack.pop() {
+            if base == key {
+                let message = format!("`{}` inherits from itself", entity.name);
+                diagnostics.push(Diagnostic::error(Target::Entity(key), message));
+                break;
+            }
+
+            if visited.insert(base) {
+                stack.extend(bases.get(&base).into_iter().flatten());
+            }
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use super::{
     button::{Button, ButtonProps, ButtonStyle},
+    menu::MenuItem,
     node::{Element, ElementWithProps},
     primitives::{
         fancy_box::{BorderOptions, FancyBox, ShadowOptions},
@@ -9,13 +10,17 @@ use super::{
     text_input::{TextInput, TextInputProps},
     Node,
 };
+#[cfg(not(target_arch = "wasm32"))]
+use crate::app::renderer::ImageOptions;
 use crate::{
     app::{
         context::{EventContext, RenderContext},
         EventTarget, Tree,
     },
+    data::{codegen::Language, diagram::Diagram},
     presentation::fonts,
 };
+use clipboard::ClipboardProvider;
 use taffy::{prelude::length, AlignItems, Layout, NodeId, Position, Style};
 
 pub struct Actionbar {
@@ -74,9 +79,42 @@ impl Element for Actionbar {
                 Button::create(ButtonProps {
                     tooltip: "Load",
                     icon: Symbol::Load,
+                    #[cfg(not(target_arch = "wasm32"))]
+                    on_click: Box::new(|ctx| {
+                        let recent = ctx.state.recent.paths();
+                        if recent.is_empty() {
+                            ctx.state.load();
+                            return;
+                        }
+
+                        let mut items = vec![MenuItem::new("Open…", |ctx| ctx.state.load())];
+
+                        // The name of the file and the directory it's in
+                        for path in recent {
+                            let name = path.file_name().unwrap_or_default().to_string_lossy();
+                            let label = match path.parent().and_then(|p| p.file_name()) {
+                                Some(dir) => format!("{name} — {}", dir.to_string_lossy()),
+                                None => name.to_string(),
+                            };
+
+                            let path = path.clone();
+                            items.push(MenuItem::new(label, move |ctx| {
+                                ctx.state.open(path.clone())
+                            }));
+                        }
+
+                        ctx.state.open_menu(items);
+                    }),
+                    #[cfg(target_arch = "wasm32")]
                     on_click: Box::new(|ctx| ctx.state.load()),
                     style: ButtonStyle::Default,
                 }),
+                Button::create(ButtonProps {
+                    tooltip: "Load from source",
+                    icon: Symbol::Class,
+                    on_click: Box::new(|ctx| ctx.state.load_source()),
+                    style: ButtonStyle::Default,
+                }),
                 Button::create(ButtonProps {
                     tooltip: "Screenshot",
                     icon: Symbol::Screenshot,
@@ -86,7 +124,48 @@ impl Element for Actionbar {
                 Button::create(ButtonProps {
                     tooltip: "Export",
                     icon: Symbol::Export,
-                    on_click: Box::new(|ctx| ctx.state.export()),
+                    on_click: Box::new(|ctx| {
+                        let mut items = Language::ALL
+                            .into_iter()
+                            .map(|language| {
+                                MenuItem::new(language.to_string(), move |ctx| {
+                                    ctx.state.export(language)
+                                })
+                            })
+                            .collect::<Vec<_>>();
+
+                        for diagram in Diagram::ALL {
+                            items.push(MenuItem::new(diagram.to_string(), move |ctx| {
+                                ctx.state.export_diagram(diagram)
+                            }));
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        for scale in ImageOptions::SCALES {
+                            items.push(MenuItem::new(format!("PNG {scale}x"), move |ctx| {
+                                ctx.state.export_image(ImageOptions::new(scale))
+                            }));
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        items.push(MenuItem::new("PNG 2x (transparent)", |ctx| {
+                            ctx.state.export_image(ImageOptions {
+                                transparent: true,
+                                ..ImageOptions::new(2.)
+                            })
+                        }));
+
+                        // Fenced, so it can be pasted straight into Markdown
+                        items.push(MenuItem::new("Copy as Mermaid", |ctx| {
+                            let diagram = Diagram::Mermaid.serialize(ctx.project);
+                            _ = ctx
+                                .state
+                                .clipboard
+                                .set_contents(format!("```mermaid\n{diagram}```\n"));
+                        }));
+
+                        ctx.state.open_menu(items);
+                    }),
                     style: ButtonStyle::Default,
                 }),
                 TextInput::create(TextInputProps {
@@ -95,6 +174,9 @@ impl Element for Actionbar {
                     placeholder: Some("Project name".to_string()),
                     getter: Box::new(|ctx| ctx.project.name.clone()),
                     setter: Box::new(|ctx, value| ctx.project.name = value.to_string()),
+                    validator: None,
+                    on_blur: None,
+                    link: None,
                 }),
             ]),
             |_, _| Actionbar {
@@ -0,0 +1,297 @@
+use super::{
+    node::Element,
+    primitives::{
+        fancy_box::{BorderOptions, FancyBox, ShadowOptions},
+        simple_box::SimpleBox,
+        text::Text,
+        traits::Draw,
+    },
+    Node,
+};
+use crate::{
+    app::{
+        context::{EventContext, GetterContext, RenderContext},
+        EventTarget, Tree,
+    },
+    geometry::{Point, Rect, Size},
+    presentation::fonts,
+};
+use taffy::{Layout, NodeId};
+use winit::{
+    event::{KeyEvent, MouseButton},
+    keyboard::{Key, NamedKey},
+    window::CursorIcon,
+};
+
+type Action = Box<dyn Fn(&mut EventContext)>;
+
+pub struct MenuItem {
+    pub label: String,
+    pub action: Action,
+
+    /// Items with a value are toggles, and show a check mark when they're on
+    pub checked: Option<bool>,
+}
+
+impl MenuItem {
+    pub fn new(label: impl Into<String>, action: impl Fn(&mut EventContext) + 'static) -> Self {
+        Self {
+            label: label.into(),
+            action: Box::new(action),
+            checked: None,
+        }
+    }
+
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+}
+
+pub struct MenuState {
+    pub origin: Point,
+    pub items: Vec<MenuItem>,
+}
+
+/// A popup menu shown at the cursor position.
+///
+/// While the menu is open, it captures all events. Clicking outside of it closes it.
+pub struct Menu {
+    layout: Layout,
+    node_id: NodeId,
+
+    hovered: Option<usize>,
+}
+
+impl Menu {
+    const FONT_SIZE: f64 = 14.;
+    const ITEM_HEIGHT: f64 = 28.;
+    const PADDING: f64 = 4.;
+    const MARGIN: f64 = 8.;
+    const CHECK_WIDTH: f64 = 18.;
+
+    /// Space reserved for the check marks (only if there are toggles)
+    fn gutter(menu: &MenuState) -> f64 {
+        match menu.items.iter().any(|item| item.checked.is_some()) {
+            true => Self::CHECK_WIDTH,
+            false => 0.,
+        }
+    }
+
+    fn measure_menu(menu: &MenuState) -> Size {
+        let width = menu
+            .items
+            .iter()
+            .map(|item| Text::measure(&item.label, Self::FONT_SIZE, fonts::inter_regular()).x)
+            .fold(0., f64::max);
+
+        Size::new(
+            width + Self::gutter(menu) + Self::FONT_SIZE * 2. + Self::PADDING * 2.,
+            menu.items.len() as f64 * Self::ITEM_HEIGHT + Self::PADDING * 2.,
+        )
+    }
+
+    fn index_at(&self, point: Point, count: usize) -> Option<usize> {
+        let rect = Rect::from(self.layout).inset_uniform(Self::PADDING);
+        if !rect.contains(point) {
+            return None;
+        }
+
+        let index = ((point.y - rect.origin.y) / Self::ITEM_HEIGHT) as usize;
+        (index < count).then_some(index)
+    }
+
+    fn close(&mut self, ctx: &mut EventContext) {
+        ctx.state.menu_state = None;
+        ctx.state.capturing = None;
+        self.hovered = None;
+
+        ctx.state.request_cursor_update();
+        ctx.state.request_redraw();
+    }
+}
+
+impl EventTarget for Menu {
+    fn update(&mut self, ctx: &mut EventContext) {
+        let Some(menu) = &ctx.state.menu_state else {
+            if ctx.state.capturing == Some(self.node_id) {
+                ctx.state.capturing = None;
+            }
+
+            return;
+        };
+
+        // Keep the menu inside of the window
+        let size = Self::measure_menu(menu);
+        let (width, height) = ctx.c.size();
+        let screen = Size::new(width as f64, height as f64) / ctx.c.scale();
+
+        let origin = Point::new(
+            menu.origin
+                .x
+                .min(screen.x - size.x - Self::MARGIN)
+                .max(Self::MARGIN),
+            menu.origin
+                .y
+                .min(screen.y - size.y - Self::MARGIN)
+                .max(Self::MARGIN),
+        );
+
+        self.layout.location = taffy::Point {
+            x: origin.x as f32,
+            y: origin.y as f32,
+        };
+        self.layout.size = taffy::Size {
+            width: size.x as f32,
+            height: size.y as f32,
+        };
+
+        if ctx.state.capturing != Some(self.node_id) {
+            ctx.state.capturing = Some(self.node_id);
+            ctx.state.hovered = Some(self.node_id);
+            ctx.state.tooltip_state = None;
+            ctx.state.request_cursor_update();
+        }
+    }
+
+    fn render(&self, RenderContext { c, state, .. }: &mut RenderContext) {
+        let Some(menu) = &state.menu_state else {
+            return;
+        };
+
+        let rect = Rect::from(self.layout);
+
+        FancyBox::new(
+            rect,
+            taffy::Rect::length(1.),
+            8.,
+            c.colors().floating_background,
+            Some(BorderOptions {
+                color: c.colors().border,
+            }),
+            Some(ShadowOptions {
+                color: c.colors().drop_shadow,
+                offset: Point::new(0., 1.),
+                blur_radius: 5.,
+            }),
+        )
+        .draw(c);
+
+        let mut item = Rect::new(
+            rect.origin + (Self::PADDING, Self::PADDING),
+            (rect.size.x - Self::PADDING * 2., Self::ITEM_HEIGHT),
+        );
+
+        let gutter = Self::gutter(menu);
+
+        for (i, MenuItem { label, checked, .. }) in menu.items.iter().enumerate() {
+            if self.hovered == Some(i) {
+                SimpleBox::new(item, 5., c.colors().hover.multiply_alpha(0.1)).draw(c);
+            }
+
+            let text = Rect::new(
+                item.origin
+                    + (
+                        Self::FONT_SIZE + gutter,
+                        (Self::ITEM_HEIGHT - Self::FONT_SIZE * 1.2) / 2.,
+                    ),
+                (
+                    item.size.x - Self::FONT_SIZE * 2. - gutter,
+                    Self::FONT_SIZE * 1.2,
+                ),
+            );
+
+            if *checked == Some(true) {
+                let check = Rect::new(text.origin - (gutter, 0.), (gutter, Self::FONT_SIZE * 1.2));
+
+                Text::new(
+                    "✓",
+                    check,
+                    Self::FONT_SIZE,
+                    fonts::inter_regular(),
+                    c.colors().accent,
+                    false,
+                )
+                .draw(c);
+            }
+
+            Text::new(
+                label,
+                text,
+                Self::FONT_SIZE,
+                fonts::inter_regular(),
+                c.colors().text,
+                true,
+            )
+            .draw(c);
+
+            item.origin.y += Self::ITEM_HEIGHT;
+        }
+    }
+
+    fn cursor(&self, _: &GetterContext) -> Option<CursorIcon> {
+        self.hovered.map(|_| CursorIcon::Pointer)
+    }
+
+    fn on_keydown(&mut self, ctx: &mut EventContext, event: KeyEvent) -> bool {
+        if event.logical_key == Key::Named(NamedKey::Escape) {
+            self.close(ctx);
+        }
+
+        true
+    }
+
+    fn on_mousedown(&mut self, _: &mut EventContext, _: MouseButton) -> bool {
+        true
+    }
+
+    fn on_mouseup(&mut self, ctx: &mut EventContext, _: MouseButton) -> bool {
+        let Some(menu) = ctx.state.menu_state.take() else {
+            return false;
+        };
+
+        let index = self.index_at(ctx.state.cursor, menu.items.len());
+        self.close(ctx);
+
+        // Clicking outside of the menu only closes it
+        if let Some(index) = index {
+            (menu.items[index].action)(ctx);
+        }
+
+        true
+    }
+
+    fn on_mousemove(&mut self, ctx: &mut EventContext, cursor: Point) -> bool {
+        let count = ctx.state.menu_state.as_ref().map_or(0, |m| m.items.len());
+        let hovered = self.index_at(cursor, count);
+
+        if hovered != self.hovered {
+            self.hovered = hovered;
+            ctx.state.request_cursor_update();
+            ctx.state.request_redraw();
+        }
+
+        true
+    }
+}
+
+impl Node for Menu {
+    fn layout(&self) -> &Layout {
+        &self.layout
+    }
+
+    fn layout_mut(&mut self) -> &mut Layout {
+        &mut self.layout
+    }
+}
+
+impl Element for Menu {
+    fn setup(tree: &mut Tree, ctx: &mut EventContext) -> NodeId {
+        tree.add_element(ctx, Default::default(), None, |node_id, _| Self {
+            layout: Default::default(),
+            node_id,
+
+            hovered: None,
+        })
+    }
+}
@@ -1,11 +1,14 @@
 pub mod actionbar;
 pub mod button;
+pub mod menu;
 pub mod node;
 pub mod primitives;
+pub mod problems;
 pub mod segmented_control;
 pub mod sidebar;
 pub mod text_element;
 pub mod text_input;
+pub mod toast;
 pub mod toolbox;
 pub mod toolbox_item;
 pub mod toolbox_item_icon;
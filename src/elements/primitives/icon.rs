@@ -35,6 +35,9 @@ pub enum Symbol {
     Method = b'Y',
     Association = b'Z',
     Export = b'a',
+    Enum = b'b',
+    Generic = b'c',
+    More = b'd',
 }
 
 impl From<Symbol> for char {
@@ -0,0 +1,426 @@
+use super::{
+    node::Element,
+    primitives::{
+        fancy_box::{BorderOptions, FancyBox, ShadowOptions},
+        simple_box::SimpleBox,
+        text::Text,
+        traits::Draw,
+    },
+    Node,
+};
+use crate::{
+    app::{
+        context::{EventContext, GetterContext, RenderContext},
+        event_target::WheelEvent,
+        EventTarget, Tree,
+    },
+    data::validation::{self, Diagnostic, Severity},
+    geometry::{Point, Rect, Size},
+    presentation::fonts,
+};
+use taffy::{Layout, NodeId};
+use winit::window::CursorIcon;
+
+#[derive(Default)]
+pub struct ProblemsState {
+    /// Whether the list is shown, or just the summary
+    pub expanded: bool,
+}
+
+/// List of the problems found by the validation, in the bottom left corner of the window.
+///
+/// Hidden while there aren't any problems.
+pub struct Problems {
+    layout: Layout,
+
+    diagnostics: Vec<Diagnostic>,
+    // The history revision the diagnostics were computed for
+    revision: Option<usize>,
+
+    // How far the list is scrolled (in pixels, the rows snap to the nearest one)
+    scroll: f64,
+}
+
+impl Problems {
+    const TITLE_SIZE: f64 = 14.;
+    const FONT_SIZE: f64 = 12.;
+    const ROW_HEIGHT: f64 = 24.;
+    const MAX_ROWS: usize = 8;
+
+    const WIDTH: f64 = 360.;
+    const PADDING: f64 = 12.;
+    const MARGIN: f64 = 12.;
+    const GAP: f64 = 8.;
+
+    // Colored dot in front of the summary and the rows
+    const DOT: f64 = 6.;
+    // Space for the entity name in a row
+    const NAME_WIDTH: f64 = 110.;
+
+    /// E.g. "2 errors, 1 warning"
+    fn summary(&self) -> String {
+        let errors = self
+            .diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+            .count();
+        let warnings = self.diagnostics.len() - errors;
+
+        match (errors, warnings) {
+            (_, 0) => Severity::Error.count(errors),
+            (0, _) => Severity::Warning.count(warnings),
+            _ => format!(
+                "{}, {}",
+                Severity::Error.count(errors),
+                Severity::Warning.count(warnings)
+            ),
+        }
+    }
+
+    /// The diagnostics scrolled into the list
+    fn rows(&self, expanded: bool) -> &[Diagnostic] {
+        if !expanded {
+            return &[];
+        }
+
+        let start = (self.scroll / Self::ROW_HEIGHT).round() as usize;
+        let start = start.min(self.diagnostics.len().saturating_sub(Self::MAX_ROWS));
+        let end = self.diagnostics.len().min(start + Self::MAX_ROWS);
+
+        &self.diagnostics[start..end]
+    }
+
+    /// How far the list can be scrolled
+    fn max_scroll(&self) -> f64 {
+        self.diagnostics.len().saturating_sub(Self::MAX_ROWS) as f64 * Self::ROW_HEIGHT
+    }
+
+    fn measure(&self, expanded: bool) -> Size {
+        if self.diagnostics.is_empty() {
+            return Size::default();
+        }
+
+        let rows = self.rows(expanded).len();
+
+        let header = Self::TITLE_SIZE * 1.2;
+        let list = match rows {
+            0 => 0.,
+            _ => Self::GAP + rows as f64 * Self::ROW_HEIGHT,
+        };
+
+        let width = match expanded {
+            true => Self::WIDTH,
+            false => {
+                let summary =
+                    Text::measure(&self.summary(), Self::TITLE_SIZE, fonts::inter_semi_bold());
+                let toggle = Text::measure("Show", Self::FONT_SIZE, fonts::inter_regular());
+
+                summary.x + toggle.x + Self::DOT + Self::GAP * 2. + Self::PADDING * 2.
+            }
+        };
+
+        Size::new(width, header + list + Self::PADDING * 2.)
+    }
+
+    /// The index of the row under the cursor (`None` for the header)
+    fn row_at(&self, cursor: Point) -> Option<usize> {
+        let y = cursor.y - self.layout.location.y as f64;
+        let top = Self::PADDING + Self::TITLE_SIZE * 1.2 + Self::GAP;
+
+        if y < top {
+            return None;
+        }
+
+        Some(((y - top) / Self::ROW_HEIGHT) as usize)
+    }
+
+    fn color(severity: Severity, ctx: &RenderContext) -> vello::peniko::Color {
+        match severity {
+            Severity::Error => ctx.c.colors().error,
+            Severity::Warning => ctx.c.colors().warning,
+        }
+    }
+}
+
+impl Node for Problems {
+    fn layout(&self) -> &Layout {
+        &self.layout
+    }
+
+    fn layout_mut(&mut self) -> &mut Layout {
+        &mut self.layout
+    }
+}
+
+impl EventTarget for Problems {
+    fn update(&mut self, ctx: &mut EventContext) {
+        let revision = ctx.state.history.revision();
+
+        if self.revision != Some(revision) {
+            self.diagnostics = validation::validate(ctx.project);
+            self.revision = Some(revision);
+            self.scroll = self.scroll.min(self.max_scroll());
+
+            ctx.state.request_redraw();
+        }
+
+        // Bottom left corner of the window
+        let size = self.measure(ctx.state.problems.expanded);
+        let (_, height) = ctx.c.size();
+        let screen = height as f64 / ctx.c.scale();
+
+        self.layout.location = taffy::Point {
+            x: Self::MARGIN as f32,
+            y: (screen - size.y - Self::MARGIN) as f32,
+        };
+        self.layout.size = taffy::Size {
+            width: size.x as f32,
+            height: size.y as f32,
+        };
+    }
+
+    fn render(&self, ctx: &mut RenderContext) {
+        if self.diagnostics.is_empty() {
+            return;
+        }
+
+        let expanded = ctx.state.problems.expanded;
+        let rect = Rect::from(self.layout);
+
+        FancyBox::new(
+            rect,
+            taffy::Rect::length(1.),
+            8.,
+            ctx.c.colors().floating_background,
+            Some(BorderOptions {
+                color: ctx.c.colors().border,
+            }),
+            Some(ShadowOptions {
+                color: ctx.c.colors().drop_shadow,
+                offset: Point::new(0., 1.),
+                blur_radius: 5.,
+            }),
+        )
+        .draw(ctx.c);
+
+        let content = Rect::new(
+            rect.origin + (Self::PADDING, Self::PADDING),
+            (rect.size.x - Self::PADDING * 2., Self::TITLE_SIZE * 1.2),
+        );
+
+        // Summary, colored by the most severe problem
+        let dot = |origin: Point, height: f64| {
+            Rect::new(
+                origin + (0., (height - Self::DOT) / 2.),
+                (Self::DOT, Self::DOT),
+            )
+        };
+
+        let severity = self.diagnostics[0].severity;
+        SimpleBox::new(
+            dot(content.origin, content.size.y),
+            Self::DOT / 2.,
+            Self::color(severity, ctx),
+        )
+        .draw(ctx.c);
+
+        Text::new(
+            &self.summary(),
+            Rect::new(content.origin + (Self::DOT + Self::GAP, 0.), content.size),
+            Self::TITLE_SIZE,
+            fonts::inter_semi_bold(),
+            ctx.c.colors().text,
+            true,
+        )
+        .draw(ctx.c);
+
+        let toggle = if expanded { "Hide" } else { "Show" };
+        let width = Text::measure(toggle, Self::FONT_SIZE, fonts::inter_regular()).x;
+
+        Text::new(
+            toggle,
+            Rect::new(
+                (
+                    content.end().x - width,
+                    content.origin.y + (Self::TITLE_SIZE - Self::FONT_SIZE) * 0.6,
+                ),
+                (width, Self::FONT_SIZE * 1.2),
+            ),
+            Self::FONT_SIZE,
+            fonts::inter_regular(),
+            ctx.c.colors().text_secondary,
+            false,
+        )
+        .draw(ctx.c);
+
+        let rows = self.rows(expanded);
+        let hovered = match rect.contains(ctx.state.cursor) {
+            true => self.row_at(ctx.state.cursor),
+            false => None,
+        };
+
+        let mut row = Rect::new(
+            content.origin + (0., content.size.y + Self::GAP),
+            (content.size.x, Self::ROW_HEIGHT),
+        );
+        let text_offset = (Self::ROW_HEIGHT - Self::FONT_SIZE * 1.2) / 2.;
+
+        for (i, diagnostic) in rows.iter().enumerate() {
+            if hovered == Some(i) {
+                let background = Rect::new(
+                    row.origin - (Self::PADDING / 2., 0.),
+                    row.size + (Self::PADDING, 0.),
+                );
+
+                let color = ctx.c.colors().hover.multiply_alpha(0.05);
+                SimpleBox::new(background, 4., color).draw(ctx.c);
+            }
+
+            SimpleBox::new(
+                dot(row.origin, Self::ROW_HEIGHT),
+                Self::DOT / 2.,
+                Self::color(diagnostic.severity, ctx),
+            )
+            .draw(ctx.c);
+
+            let name = diagnostic
+                .target
+                .entity(ctx.project)
+                .and_then(|key| ctx.project.entities.get(key))
+                .map(|entity| entity.name.as_str())
+                .filter(|name| !name.is_empty())
+                .unwrap_or("Untitled");
+
+            let origin = row.origin + (Self::DOT + Self::GAP, text_offset);
+            let width = Text::measure(name, Self::FONT_SIZE, fonts::inter_semi_bold())
+                .x
+                .min(Self::NAME_WIDTH);
+
+            Text::new(
+                name,
+                Rect::new(origin, (width, Self::FONT_SIZE * 1.2)),
+                Self::FONT_SIZE,
+                fonts::inter_semi_bold(),
+                ctx.c.colors().text,
+                true,
+            )
+            .draw(ctx.c);
+
+            let origin = origin + (width + Self::GAP, 0.);
+            Text::new(
+                &diagnostic.message,
+                Rect::new(origin, (row.end().x - origin.x, Self::FONT_SIZE * 1.2)),
+                Self::FONT_SIZE,
+                fonts::inter_regular(),
+                ctx.c.colors().text_secondary,
+                true,
+            )
+            .draw(ctx.c);
+
+            row.origin.y += Self::ROW_HEIGHT;
+        }
+
+        // Scrollbar in the right padding, only while some of the rows are hidden
+        let max = self.max_scroll();
+        if !rows.is_empty() && max > 0. {
+            let height = Self::MAX_ROWS as f64 * Self::ROW_HEIGHT;
+            let ratio = height / (height + max);
+
+            let scrollbar = Rect::new(
+                (
+                    rect.end().x - Self::PADDING / 2. - 2.,
+                    row.origin.y - height + self.scroll * ratio,
+                ),
+                (4., height * ratio),
+            );
+
+            SimpleBox::new(scrollbar, 2., ctx.c.colors().border).draw(ctx.c);
+        }
+    }
+
+    fn cursor(&self, ctx: &GetterContext) -> Option<CursorIcon> {
+        if self.diagnostics.is_empty() {
+            return None;
+        }
+
+        let rows = self.rows(ctx.state.problems.expanded);
+        match self.row_at(ctx.state.cursor) {
+            Some(row) if row >= rows.len() => None,
+            _ => Some(CursorIcon::Pointer),
+        }
+    }
+
+    fn on_click(&mut self, ctx: &mut EventContext) -> bool {
+        if self.diagnostics.is_empty() {
+            return false;
+        }
+
+        let rows = self.rows(ctx.state.problems.expanded);
+
+        match self.row_at(ctx.state.cursor) {
+            // The header toggles the list
+            None => ctx.state.problems.expanded = !ctx.state.problems.expanded,
+
+            // Select the entity and move it into view
+            Some(row) => {
+                let Some(entity) = rows.get(row).and_then(|d| d.target.entity(ctx.project)) else {
+                    return true;
+                };
+
+                ctx.state.select(Some(entity));
+                ctx.state.workspace.focus = Some(entity);
+            }
+        }
+
+        ctx.state.request_cursor_update();
+        ctx.state.request_redraw();
+
+        true
+    }
+
+    fn on_mousemove(&mut self, ctx: &mut EventContext, _: Point) -> bool {
+        if self.diagnostics.is_empty() {
+            return false;
+        }
+
+        // Highlight the hovered row
+        ctx.state.request_cursor_update();
+        ctx.state.request_redraw();
+
+        true
+    }
+
+    fn on_mouseleave(&mut self, ctx: &mut EventContext) -> bool {
+        ctx.state.request_redraw();
+
+        true
+    }
+
+    fn on_wheel(&mut self, ctx: &mut EventContext, event: WheelEvent) -> bool {
+        if self.diagnostics.is_empty() {
+            return false;
+        }
+
+        // Scrolling over the panel shouldn't move the diagram, even if the list doesn't scroll
+        if ctx.state.problems.expanded {
+            self.scroll = (self.scroll - event.delta.y).clamp(0., self.max_scroll());
+
+            ctx.state.request_cursor_update();
+            ctx.state.request_redraw();
+        }
+
+        true
+    }
+}
+
+impl Element for Problems {
+    fn setup(tree: &mut Tree, ctx: &mut EventContext) -> NodeId {
+        tree.add_element(ctx, Default::default(), None, |_, _| Self {
+            layout: Default::default(),
+
+            diagnostics: Vec::new(),
+            revision: None,
+            scroll: 0.,
+        })
+    }
+}
@@ -1,13 +1,17 @@
 use super::list::Countable;
 use crate::{
     app::{context::EventContext, EventTarget, Tree},
-    data::entity::AccessModifier,
+    data::{
+        entity::{AccessModifier, EntityType, FieldKind, Mutability},
+        signature,
+    },
     elements::{
         button::{Button, ButtonProps, ButtonStyle},
+        menu::MenuItem,
         node::ElementWithProps,
         primitives::icon::Symbol,
         segmented_control::{SegmentedControl, SegmentedControlProps},
-        sidebar::sidebar_entity,
+        sidebar::{follow_reference, sidebar_entity},
         text_input::{TextInput, TextInputProps},
         Node,
     },
@@ -26,6 +30,10 @@ impl Countable for SidebarField {
             .map(|ent| ent.fields.len())
             .unwrap_or(0)
     }
+
+    fn visible(ctx: &EventContext) -> bool {
+        sidebar_entity!(ctx => get).is_some_and(|ent| ent.entity_type != EntityType::Enum)
+    }
 }
 
 impl EventTarget for SidebarField {}
@@ -101,17 +109,108 @@ impl ElementWithProps for SidebarField {
                         if let Some(field) =
                             sidebar_entity!(ctx => get_mut).and_then(|e| e.fields.get_mut(idx))
                         {
-                            let Some((name, ty)) = str.split_once(':') else {
+                            let Ok(signature) = signature::parse_field(str) else {
                                 return;
                             };
 
-                            field.name = name.trim().to_string();
-                            field.r#type = ty.trim().to_string();
+                            field.modifier = signature.modifier.unwrap_or(field.modifier);
+                            field.name = signature.name;
+                            field.r#type = signature.r#type;
                         }
                     }),
                     size: 16.,
                     font: fonts::jbmono_regular(),
                     placeholder: None,
+                    validator: Some(Box::new(|_, str| signature::parse_field(str).map(|_| ()))),
+                    on_blur: None,
+                    link: Some(Box::new(follow_reference)),
+                }),
+                // Modifiers
+                Button::create(ButtonProps {
+                    tooltip: "Modifiers",
+                    icon: Symbol::More,
+                    on_click: Box::new(move |ctx| {
+                        let Some(field) =
+                            sidebar_entity!(ctx => get).and_then(|e| e.fields.get(idx))
+                        else {
+                            return;
+                        };
+
+                        let mutability = |mutability: Mutability| {
+                            MenuItem::new(mutability.to_string(), move |ctx| {
+                                if let Some(field) = sidebar_entity!(ctx => get_mut)
+                                    .and_then(|e| e.fields.get_mut(idx))
+                                {
+                                    // The mutabilities are exclusive, clicking again unsets it
+                                    field.mutability = match field.mutability == mutability {
+                                        true => Mutability::Mutable,
+                                        false => mutability,
+                                    };
+                                }
+                            })
+                            .checked(field.mutability == mutability)
+                        };
+
+                        let mut items = vec![
+                            MenuItem::new("Property", move |ctx| {
+                                if let Some(field) = sidebar_entity!(ctx => get_mut)
+                                    .and_then(|e| e.fields.get_mut(idx))
+                                {
+                                    field.kind = match field.kind {
+                                        FieldKind::Field => FieldKind::Property,
+                                        FieldKind::Property => FieldKind::Field,
+                                    };
+
+                                    // Fields don't have an init accessor
+                                    if field.kind == FieldKind::Field
+                                        && field.mutability == Mutability::Init
+                                    {
+                                        field.mutability = Mutability::ReadOnly;
+                                    }
+                                }
+                            })
+                            .checked(field.kind == FieldKind::Property),
+                            MenuItem::new("Static", move |ctx| {
+                                if let Some(field) = sidebar_entity!(ctx => get_mut)
+                                    .and_then(|e| e.fields.get_mut(idx))
+                                {
+                                    field.is_static = !field.is_static;
+                                }
+                            })
+                            .checked(field.is_static),
+                            mutability(Mutability::ReadOnly),
+                        ];
+
+                        if field.kind == FieldKind::Property {
+                            items.push(mutability(Mutability::Init));
+                        }
+
+                        items.push(mutability(Mutability::Const));
+
+                        // The type references another entity that isn't connected yet
+                        let reference = ctx
+                            .state
+                            .sidebar
+                            .entity
+                            .and_then(|key| ctx.project.field_reference(key, idx));
+
+                        if let Some((target, _)) = reference {
+                            let name = &ctx.project.entities[target].name;
+
+                            items.push(MenuItem::new(
+                                format!("Associate with {name}"),
+                                move |ctx| {
+                                    if let Some(key) = ctx.state.sidebar.entity {
+                                        ctx.project.associate_field(key, idx);
+                                        ctx.state.request_redraw();
+                                    }
+                                },
+                            ));
+                        }
+
+                        ctx.state.open_menu(items);
+                    }),
+                    style: ButtonStyle::Segmented,
                 }),
                 // Delete button
                 Button::create(ButtonProps {
@@ -5,8 +5,8 @@ use crate::{
         button::{Button, ButtonProps, ButtonStyle},
         node::ElementWithProps,
         primitives::icon::Symbol,
-        sidebar::sidebar_entity,
-        text_element::{TextElement, TextElementProps},
+        sidebar::{follow_reference, sidebar_entity},
+        text_input::{TextInput, TextInputProps},
         Node,
     },
     presentation::fonts,
@@ -32,6 +32,10 @@ impl Countable for SidebarImplementation {
             .map(|ent| ent.implements.len())
             .unwrap_or(0)
     }
+
+    fn visible(ctx: &EventContext) -> bool {
+        sidebar_entity!(ctx => get).is_some_and(|ent| ent.entity_type.can_implement())
+    }
 }
 
 impl EventTarget for SidebarImplementation {}
@@ -54,28 +58,27 @@ impl ElementWithProps for SidebarImplementation {
             ctx,
             Self::STYLE,
             Some(vec![
-                // Label
-                TextElement::create(TextElementProps {
+                // Name with the type arguments (only the arguments can be edited)
+                TextInput::create(TextInputProps {
                     getter: Box::new(move |ctx| {
-                        let Some(ent) = sidebar_entity!(ctx => get) else {
-                            return "".to_string();
-                        };
-
-                        let Some(conn) = ent
-                            .implements
-                            .get(idx)
-                            .and_then(|key| ctx.project.connections.get(*key))
-                        else {
-                            return "".to_string();
-                        };
-
-                        let other = conn.other(ent.key);
-                        let other = ctx.project.entities.get(other.entity).unwrap();
-
-                        other.name.clone()
+                        match sidebar_entity!(ctx => get).and_then(|e| e.implements.get(idx)) {
+                            Some(&connection) => ctx.project.reference(connection),
+                            None => "".to_string(),
+                        }
+                    }),
+                    setter: Box::new(move |ctx, str| {
+                        if let Some(&connection) =
+                            sidebar_entity!(ctx => get).and_then(|e| e.implements.get(idx))
+                        {
+                            ctx.project.set_arguments(connection, str);
+                        }
                     }),
                     size: 16.,
                     font: fonts::jbmono_regular(),
+                    placeholder: None,
+                    validator: None,
+                    on_blur: None,
+                    link: Some(Box::new(follow_reference)),
                 }),
                 // Remove button
                 Button::create(ButtonProps {
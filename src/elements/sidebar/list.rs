@@ -4,27 +4,66 @@ use crate::{
     elements::{node::ElementWithProps, Node},
 };
 use taffy::{
-    prelude::{auto, length, percent},
-    FlexDirection, Layout, NodeId, Size, Style, TraversePartialTree,
+    Dimension, Display, FlexDirection, Layout, LengthPercentage, NodeId, Size, Style,
+    TraversePartialTree,
 };
 
 pub trait Countable {
     fn count(ctx: &EventContext) -> usize;
+
+    /// Whether the list applies to the selected entity (e.g. enums don't have methods)
+    fn visible(_ctx: &EventContext) -> bool {
+        true
+    }
 }
 
 pub struct List<T: ElementWithProps<Props = usize> + Countable> {
     layout: Layout,
+    node_id: NodeId,
     container: NodeId,
 
     _phantom: std::marker::PhantomData<T>,
 }
 
+impl<T: ElementWithProps<Props = usize> + Countable> List<T> {
+    const STYLE: Style = Style {
+        max_size: Size {
+            width: Dimension::Percent(1.),
+            height: Dimension::Auto,
+        },
+        flex_direction: FlexDirection::Column,
+        gap: Size {
+            width: LengthPercentage::Length(8.),
+            height: LengthPercentage::Length(8.),
+        },
+        ..Style::DEFAULT
+    };
+}
+
 impl<T: ElementWithProps<Props = usize> + Countable> EventTarget for List<T> {
     fn update(&mut self, ctx: &mut EventContext) {
-        // Make sure the child count matches the connection count
+        let list = self.node_id;
         let node_id = self.container;
 
         ctx.state.modify_tree(move |tree, ctx| {
+            // Hide the whole list (including the label) if it doesn't apply to the entity
+            let display = if T::visible(ctx) {
+                Display::Flex
+            } else {
+                Display::None
+            };
+
+            if tree.style(list).unwrap().display != display {
+                let style = Style {
+                    display,
+                    ..Self::STYLE
+                };
+
+                tree.set_style(list, style).unwrap();
+                ctx.state.request_redraw();
+            }
+
+            // Make sure the child count matches the connection count
             let target = T::count(ctx);
             let count = tree.child_count(node_id);
 
@@ -62,29 +101,20 @@ impl<T: ElementWithProps<Props = usize> + Countable + 'static> ElementWithProps
     type Props = CategoryProps;
 
     fn setup(tree: &mut Tree, ctx: &mut EventContext, label: CategoryProps) -> NodeId {
-        let style = Style {
-            max_size: Size {
-                width: percent(1.),
-                height: auto(),
-            },
-            flex_direction: FlexDirection::Column,
-            gap: length(8.),
-            ..<_>::default()
-        };
-
-        let container = tree.new_leaf(style.clone()).unwrap();
+        let container = tree.new_leaf(Self::STYLE).unwrap();
 
         tree.add_element(
             ctx,
-            style,
+            Self::STYLE,
             Some(vec![
                 // Label
                 Category::create(label),
                 // Items
                 Box::new(move |_, _| container),
             ]),
-            |_, _| Self {
+            |node_id, _| Self {
                 layout: <_>::default(),
+                node_id,
                 container,
                 _phantom: std::marker::PhantomData,
             },
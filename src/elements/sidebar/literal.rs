@@ -0,0 +1,104 @@
+use super::list::Countable;
+use crate::{
+    app::{context::EventContext, EventTarget, Tree},
+    data::{entity::EntityType, signature},
+    elements::{
+        button::{Button, ButtonProps, ButtonStyle},
+        node::ElementWithProps,
+        primitives::icon::Symbol,
+        sidebar::sidebar_entity,
+        text_input::{TextInput, TextInputProps},
+        Node,
+    },
+    presentation::fonts,
+};
+use taffy::{
+    prelude::{auto, length, percent},
+    Layout, NodeId, Size, Style,
+};
+
+pub struct SidebarLiteral(Layout);
+
+impl Countable for SidebarLiteral {
+    fn count(ctx: &EventContext) -> usize {
+        sidebar_entity!(ctx => get)
+            .map(|ent| ent.literals.len())
+            .unwrap_or(0)
+    }
+
+    fn visible(ctx: &EventContext) -> bool {
+        sidebar_entity!(ctx => get).is_some_and(|ent| ent.entity_type == EntityType::Enum)
+    }
+}
+
+impl EventTarget for SidebarLiteral {}
+
+impl Node for SidebarLiteral {
+    fn layout(&self) -> &Layout {
+        &self.0
+    }
+
+    fn layout_mut(&mut self) -> &mut Layout {
+        &mut self.0
+    }
+}
+
+impl ElementWithProps for SidebarLiteral {
+    type Props = usize; // idx
+
+    fn setup(tree: &mut Tree, ctx: &mut EventContext, idx: usize) -> NodeId {
+        tree.add_element(
+            ctx,
+            Style {
+                max_size: Size {
+                    width: percent(1.),
+                    height: auto(),
+                },
+                gap: length(4.),
+                ..<_>::default()
+            },
+            Some(vec![
+                // Name (and the optional value)
+                TextInput::create(TextInputProps {
+                    getter: Box::new(move |ctx| {
+                        if let Some(literal) =
+                            sidebar_entity!(ctx => get).and_then(|e| e.literals.get(idx))
+                        {
+                            literal.to_string()
+                        } else {
+                            "".to_string()
+                        }
+                    }),
+                    setter: Box::new(move |ctx, str| {
+                        if let Some(literal) =
+                            sidebar_entity!(ctx => get_mut).and_then(|e| e.literals.get_mut(idx))
+                        {
+                            if let Ok(parsed) = signature::parse_literal(str) {
+                                *literal = parsed;
+                            }
+                        }
+                    }),
+                    size: 16.,
+                    font: fonts::jbmono_regular(),
+                    placeholder: None,
+                    validator: Some(Box::new(|_, str| signature::parse_literal(str).map(|_| ()))),
+                    on_blur: None,
+                    link: None,
+                }),
+                // Delete button
+                Button::create(ButtonProps {
+                    tooltip: "Delete literal",
+                    icon: Symbol::Trash,
+                    on_click: Box::new(move |ctx| {
+                        if let Some(entity) = sidebar_entity!(ctx => get_mut) {
+                            entity.literals.remove(idx);
+                            ctx.state.request_tooltip_update();
+                        }
+                    }),
+                    style: ButtonStyle::Segmented,
+                }),
+            ]),
+            |_, _| Self(<_>::default()),
+        )
+    }
+}
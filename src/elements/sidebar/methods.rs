@@ -1,13 +1,19 @@
 use super::list::Countable;
 use crate::{
     app::{context::EventContext, EventTarget, Tree},
-    data::entity::AccessModifier,
+    data::{
+        entity::{
+            AccessModifier, EntityType, Inheritance, Method, MethodKind, Parameter, TypeParameter,
+        },
+        signature,
+    },
     elements::{
         button::{Button, ButtonProps, ButtonStyle},
+        menu::MenuItem,
         node::ElementWithProps,
         primitives::icon::Symbol,
         segmented_control::{SegmentedControl, SegmentedControlProps},
-        sidebar::sidebar_entity,
+        sidebar::{follow_reference, sidebar_entity},
         text_input::{TextInput, TextInputProps},
         Node,
     },
@@ -26,6 +32,10 @@ impl Countable for SidebarMethod {
             .map(|ent| ent.methods.len())
             .unwrap_or(0)
     }
+
+    fn visible(ctx: &EventContext) -> bool {
+        sidebar_entity!(ctx => get).is_some_and(|ent| ent.entity_type != EntityType::Enum)
+    }
 }
 
 impl EventTarget for SidebarMethod {}
@@ -90,41 +100,164 @@ impl ElementWithProps for SidebarMethod {
                         if let Some(method) =
                             sidebar_entity!(ctx => get).and_then(|e| e.methods.get(idx))
                         {
+                            // Constructors don't have a return type (or type parameters)
+                            if method.is_constructor() {
+                                let args = Parameter::list(&method.arguments);
+                                return format!("{}({args})", method.name);
+                            }
+
+                            let constraints = method
+                                .generics
+                                .iter()
+                                .filter(|p| !p.constraints.is_empty())
+                                .map(|p| format!(" where {p}"))
+                                .collect::<String>();
+
+                            // Methods without a return type don't need the colon
+                            let ret = match method.return_type.as_str() {
+                                "" => String::new(),
+                                ret => format!(": {ret}"),
+                            };
+
                             format!(
-                                "{name}({args}): {ret}",
+                                "{name}{generics}({args}){ret}{constraints}",
                                 name = method.name,
-                                args = method.arguments.join(", "),
-                                ret = method.return_type
+                                generics = TypeParameter::list(&method.generics),
+                                args = Parameter::list(&method.arguments),
                             )
                         } else {
                             "".to_string()
                         }
                     }),
                     setter: Box::new(move |ctx, str| {
-                        if let Some(method) =
-                            sidebar_entity!(ctx => get_mut).and_then(|e| e.methods.get_mut(idx))
-                        {
-                            let Some((name, args_ret)) = str.split_once('(') else {
-                                return;
-                            };
+                        let Some(entity) = sidebar_entity!(ctx => get_mut) else {
+                            return;
+                        };
 
-                            let Some((args, ret)) = args_ret.split_once("):") else {
-                                return;
-                            };
+                        let name = entity.name.clone();
+                        let Some(method) = entity.methods.get_mut(idx) else {
+                            return;
+                        };
 
-                            method.name = name.trim().to_string();
-                            method.arguments = if args.trim().is_empty() {
-                                vec![]
-                            } else {
-                                args.split(',').map(|s| s.trim().to_string()).collect()
-                            };
+                        let signature = match method.is_constructor() {
+                            true => signature::parse_constructor(str, &name),
+                            false => signature::parse_method(str),
+                        };
 
-                            method.return_type = ret.trim().to_string();
-                        }
+                        let Ok(signature) = signature else {
+                            return;
+                        };
+
+                        method.modifier = signature.modifier.unwrap_or(method.modifier);
+                        method.name = signature.name;
+                        method.generics = signature.generics;
+                        method.arguments = signature.arguments;
+                        method.return_type = signature.return_type;
                     }),
                     size: 16.,
                     font: fonts::jbmono_regular(),
                     placeholder: None,
+                    validator: Some(Box::new(move |ctx, str| {
+                        let Some(entity) = sidebar_entity!(ctx => get) else {
+                            return Ok(());
+                        };
+
+                        match entity.methods.get(idx) {
+                            Some(method) if method.is_constructor() => {
+                                signature::parse_constructor(str, &entity.name).map(|_| ())
+                            }
+                            _ => signature::parse_method(str).map(|_| ()),
+                        }
+                    })),
+                    on_blur: None,
+                    link: Some(Box::new(follow_reference)),
+                }),
+                // Modifiers
+                Button::create(ButtonProps {
+                    tooltip: "Modifiers",
+                    icon: Symbol::More,
+                    on_click: Box::new(move |ctx| {
+                        let Some(entity) = sidebar_entity!(ctx => get) else {
+                            return;
+                        };
+
+                        let Some(method) = entity.methods.get(idx) else {
+                            return;
+                        };
+
+                        let interface = entity.entity_type == EntityType::Interface;
+
+                        // Interfaces can't be instantiated
+                        let mut items = match interface {
+                            true => vec![],
+                            false => vec![MenuItem::new("Constructor", move |ctx| {
+                                let Some(entity) = sidebar_entity!(ctx => get_mut) else {
+                                    return;
+                                };
+
+                                let name = entity.name.clone();
+                                if let Some(method) = entity.methods.get_mut(idx) {
+                                    *method = match method.is_constructor() {
+                                        true => Method {
+                                            kind: MethodKind::Method,
+                                            ..std::mem::take(method)
+                                        },
+                                        false => Method {
+                                            modifier: method.modifier,
+                                            ..Method::constructor(&name, method.arguments.clone())
+                                        },
+                                    };
+                                }
+                            })
+                            .checked(method.is_constructor())],
+                        };
+
+                        // Constructors can't have any other modifiers
+                        if method.is_constructor() {
+                            ctx.state.open_menu(items);
+                            return;
+                        }
+
+                        // Static methods can't be overridden (and the other way around)
+                        items.push(
+                            MenuItem::new("Static", move |ctx| {
+                                if let Some(method) = sidebar_entity!(ctx => get_mut)
+                                    .and_then(|e| e.methods.get_mut(idx))
+                                {
+                                    method.is_static = !method.is_static;
+                                    method.inheritance = Inheritance::None;
+                                }
+                            })
+                            .checked(method.is_static),
+                        );
+
+                        // Interface methods are always abstract
+                        if !interface {
+                            for inheritance in [
+                                Inheritance::Abstract,
+                                Inheritance::Virtual,
+                                Inheritance::Override,
+                            ] {
+                                let item = MenuItem::new(inheritance.to_string(), move |ctx| {
+                                    if let Some(method) = sidebar_entity!(ctx => get_mut)
+                                        .and_then(|e| e.methods.get_mut(idx))
+                                    {
+                                        method.is_static = false;
+                                        method.inheritance = match method.inheritance == inheritance
+                                        {
+                                            true => Inheritance::None,
+                                            false => inheritance,
+                                        };
+                                    }
+                                });
+
+                                items.push(item.checked(method.inheritance == inheritance));
+                            }
+                        }
+
+                        ctx.state.open_menu(items);
+                    }),
+                    style: ButtonStyle::Segmented,
                 }),
                 // Delete button
                 Button::create(ButtonProps {
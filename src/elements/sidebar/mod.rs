@@ -18,7 +18,7 @@ use crate::{
         context::{EventContext, RenderContext},
         EventTarget, Tree,
     },
-    data::project::EntityKey,
+    data::{entity::TypeParameter, project::EntityKey},
     elements::{node::Element, toolbox_item::Tool},
     geometry::Rect,
 };
@@ -28,10 +28,12 @@ use derive_macros::AnimatedElement;
 use field::SidebarField;
 use implementation::SidebarImplementation;
 use list::List;
+use literal::SidebarLiteral;
 use methods::SidebarMethod;
 use name::sidebar_name;
 use parent::SidebarParent;
 use r#type::SidebarType;
+use selection::SidebarSelection;
 use std::time::Duration;
 use taffy::{
     prelude::{auto, length, zero},
@@ -39,22 +41,39 @@ use taffy::{
     FlexDirection::Column,
     Layout, NodeId, Position, Size, Style,
 };
+use type_parameter::SidebarTypeParameter;
 
 mod category;
 mod connection;
 mod field;
 mod implementation;
 mod list;
+mod literal;
 mod methods;
 mod name;
 mod parent;
+mod selection;
 mod r#type;
+mod type_parameter;
 
 #[derive(Default)]
 pub struct SidebarState {
     // Used for animating the sidebar in and out
     // Will contain the old entity id when the sidebar is closing
     pub entity: Option<EntityKey>,
+
+    // Number of selected entities shown in the bulk actions panel (0 if it isn't shown)
+    pub selection: usize,
+
+    // Entity renamed in the sidebar with its original name, usages are updated once the name input loses focus
+    pub renamed: Option<(EntityKey, String)>,
+}
+
+impl SidebarState {
+    #[inline]
+    fn visible(entity: Option<EntityKey>, selection: usize) -> bool {
+        entity.is_some() || selection > 0
+    }
 }
 
 // Macro for getting the entity from the sidebar state, cuz it's long
@@ -69,14 +88,54 @@ macro_rules! sidebar_entity {
 
 pub(super) use sidebar_entity;
 
+/// Selects the entity referenced by a type and moves it into view (main modifier + click)
+fn follow_reference(ctx: &mut EventContext, name: &str) -> bool {
+    let Some(entity) = ctx.project.find_entity(name) else {
+        return false;
+    };
+
+    ctx.state.select(Some(entity));
+    ctx.state.workspace.focus = Some(entity);
+    ctx.state.request_redraw();
+
+    true
+}
+
 #[derive(AnimatedElement)]
 pub struct Sidebar {
     layout: Layout,
+    node_id: NodeId,
+
+    /// Detached node holding the children that aren't currently shown
+    stash: NodeId,
+    bulk: bool,
 
     position: AnimatedProperty<StandardAnimation<f32>>,
     scroll: AnimatedProperty<DeltaAnimation<f32>>,
 }
 
+impl Sidebar {
+    /// Swaps the entity panel with the bulk actions panel (or the other way around)
+    fn show_bulk_actions(&mut self, ctx: &mut EventContext, bulk: bool) {
+        if self.bulk == bulk {
+            return;
+        }
+
+        self.bulk = bulk;
+
+        let (node, stash) = (self.node_id, self.stash);
+        ctx.state.modify_tree(move |tree, ctx| {
+            let shown = tree.children(node).unwrap();
+            let hidden = tree.children(stash).unwrap();
+
+            tree.set_children(node, &hidden).unwrap();
+            tree.set_children(stash, &shown).unwrap();
+
+            ctx.state.request_redraw();
+        });
+    }
+}
+
 impl EventTarget for Sidebar {
     fn update(&mut self, ctx: &mut EventContext) {
         let animate = self.animate();
@@ -90,19 +149,34 @@ impl EventTarget for Sidebar {
 
         macro_rules! cached {
             () => {
-                ctx.state.sidebar.entity
+                (ctx.state.sidebar.entity, ctx.state.sidebar.selection)
             };
         }
 
         macro_rules! real {
             () => {
                 match ctx.state.tool {
-                    Tool::Select => ctx.state.selected_entity,
-                    _ => None
+                    Tool::Select if ctx.state.selection.len() > 1 => {
+                        (None, ctx.state.selection.len())
+                    }
+                    Tool::Select => (ctx.state.selected_entity, 0),
+                    _ => (None, 0),
                 }
             };
         }
 
+        macro_rules! visible {
+            ($value:expr) => {{
+                let (entity, selection) = $value;
+                SidebarState::visible(entity, selection)
+            }};
+        }
+
+        // Changing the number of selected entities doesn't animate the sidebar
+        if cached!().1 > 0 && real!().1 > 0 {
+            ctx.state.sidebar.selection = real!().1;
+        }
+
         if animate {
             ctx.state.request_redraw();
 
@@ -115,19 +189,20 @@ impl EventTarget for Sidebar {
         }
 
         // If the animation is finished, remove the old entity and rerun update
-        if cached!().is_some() && offset == 1. {
-            cached!() = None;
+        if visible!(cached!()) && offset == 1. {
+            (ctx.state.sidebar.entity, ctx.state.sidebar.selection) = (None, 0);
             self.scroll.reset(0.);
 
             return self.update(ctx);
         }
 
         // If a new entity is selected, update the sidebar
-        if real!().is_some() && real!() != cached!() {
-            if cached!().is_some() {
+        if visible!(real!()) && real!() != cached!() {
+            if visible!(cached!()) {
                 self.position.set(1.);
             } else {
-                cached!() = real!();
+                (ctx.state.sidebar.entity, ctx.state.sidebar.selection) = real!();
+                self.show_bulk_actions(ctx, ctx.state.sidebar.selection > 0);
                 self.position.set(0.);
             }
 
@@ -135,18 +210,18 @@ impl EventTarget for Sidebar {
         }
 
         // Hide the sidebar if no entity is selected
-        if cached!().is_none() {
+        if !visible!(cached!()) {
             self.layout.size = zero();
         }
         // Animate out the sidebar if the entity is deselected
-        else if real!().is_none() {
+        else if !visible!(real!()) {
             self.position.set(1.);
             ctx.state.request_redraw();
         }
     }
 
     fn render(&self, RenderContext { c, state, .. }: &mut RenderContext) {
-        if state.sidebar.entity.is_none() {
+        if !SidebarState::visible(state.sidebar.entity, state.sidebar.selection) {
             return;
         }
 
@@ -226,6 +301,12 @@ impl Node for Sidebar {
 
 impl Element for Sidebar {
     fn setup(tree: &mut Tree, ctx: &mut EventContext) -> NodeId {
+        // Shown instead of the entity details when multiple entities are selected
+        let selection = SidebarSelection::setup(tree, ctx);
+        let stash = tree
+            .new_with_children(Style::default(), &[selection])
+            .unwrap();
+
         tree.add_element(
             ctx,
             Style {
@@ -253,6 +334,29 @@ impl Element for Sidebar {
                 SidebarType::create(),
                 // Name
                 sidebar_name(),
+                // Type parameters
+                List::<SidebarTypeParameter>::create(CategoryProps {
+                    icon: Symbol::Generic,
+                    name: "Type parameters".to_string(),
+                    add: Box::new(|ctx| {
+                        if let Some(entity) = sidebar_entity!(ctx => get_mut) {
+                            // Named like in C# (`T`, then `T2`, `T3`...)
+                            let name = (1..)
+                                .map(|i| match i {
+                                    1 => "T".to_string(),
+                                    i => format!("T{i}"),
+                                })
+                                .find(|name| !entity.generics.iter().any(|p| p.name == *name))
+                                .unwrap();
+
+                            entity.generics.push(TypeParameter {
+                                name,
+                                constraints: vec![],
+                            });
+                            ctx.state.request_redraw();
+                        }
+                    }),
+                }),
                 // Parent
                 SidebarParent::create(),
                 // Implementations
@@ -284,6 +388,17 @@ impl Element for Sidebar {
                         }
                     }),
                 }),
+                // Literals (only for enums)
+                List::<SidebarLiteral>::create(CategoryProps {
+                    icon: Symbol::Enum,
+                    name: "Literals".to_string(),
+                    add: Box::new(|ctx| {
+                        if let Some(entity) = sidebar_entity!(ctx => get_mut) {
+                            entity.literals.push(Default::default());
+                            ctx.state.request_redraw();
+                        }
+                    }),
+                }),
                 // Methods
                 List::<SidebarMethod>::create(CategoryProps {
                     icon: Symbol::Method,
@@ -296,8 +411,12 @@ impl Element for Sidebar {
                     }),
                 }),
             ]),
-            |_, _| Self {
+            |node_id, _| Self {
                 layout: Default::default(),
+                node_id,
+
+                stash,
+                bulk: false,
 
                 position: AnimatedProperty::new(StandardAnimation::initialized(
                     1., // 1 = offscreen, 0 = fully visible
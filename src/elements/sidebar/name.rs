@@ -1,6 +1,8 @@
 use super::sidebar_entity;
 use crate::{
+    app::context::EventContext,
     elements::{
+        menu::MenuItem,
         node::{CurriedSetup, ElementWithProps},
         text_input::{TextInput, TextInputProps},
     },
@@ -17,12 +19,55 @@ pub fn sidebar_name() -> Box<CurriedSetup> {
             }
         }),
         setter: Box::new(|ctx, str| {
-            if let Some(entity) = sidebar_entity!(ctx => get_mut) {
-                entity.name = str.to_string();
+            let Some(entity) = sidebar_entity!(ctx => get_mut) else {
+                return;
+            };
+
+            // Remember the name from before the editing started
+            let renamed = &mut ctx.state.sidebar.renamed;
+            if renamed.as_ref().is_none_or(|(key, _)| *key != entity.key) {
+                *renamed = Some((entity.key, entity.name.clone()));
             }
+
+            entity.rename(str);
         }),
         placeholder: Some("Untitled".to_string()),
         size: 24.,
         font: fonts::jbmono_bold(),
+        validator: None,
+        on_blur: Some(Box::new(offer_rename)),
+        link: None,
     })
 }
+
+/// Offers to update the types referencing the entity by its old name
+fn offer_rename(ctx: &mut EventContext) {
+    let Some((key, old)) = ctx.state.sidebar.renamed.take() else {
+        return;
+    };
+
+    let Some(entity) = ctx.project.entities.get(key) else {
+        return;
+    };
+
+    // Another entity might still be using the old name
+    let new = entity.name.clone();
+    if new == old || new.is_empty() || ctx.project.find_entity(&old).is_some() {
+        return;
+    }
+
+    let usages = ctx.project.usages(&old);
+    let noun = match usages {
+        0 => return,
+        1 => "usage",
+        _ => "usages",
+    };
+
+    ctx.state.open_menu(vec![
+        MenuItem::new(format!("Update {usages} {noun} of {old}"), move |ctx| {
+            ctx.project.rename_usages(&old, &new);
+            ctx.state.request_redraw();
+        }),
+        MenuItem::new("Keep the old references", |_| {}),
+    ]);
+}
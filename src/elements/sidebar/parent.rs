@@ -1,15 +1,14 @@
 use super::{
     category::{Category, CategoryProps},
-    sidebar_entity,
+    follow_reference, sidebar_entity,
 };
 use crate::{
     app::{context::EventContext, EventTarget, Tree},
-    data::entity::EntityType,
     elements::{
         button::{Button, ButtonProps, ButtonStyle},
         node::{Element, ElementWithProps},
         primitives::icon::Symbol,
-        text_element::{TextElement, TextElementProps},
+        text_input::{TextInput, TextInputProps},
         toolbox_item::Tool,
         Node,
     },
@@ -43,14 +42,14 @@ impl EventTarget for SidebarParent {
         let node_id = self.node_id;
         let content = self.content;
 
-        // Completely hide if the entity is an interface
+        // Completely hide if the entity can't have a parent (interfaces and enums)
         // Hide the content if the entity has no parent
         ctx.state.modify_tree(move |tree, ctx| {
             let display;
             let content_display;
 
             if let Some(ent) = sidebar_entity!(ctx => get) {
-                display = if ent.entity_type == EntityType::Interface {
+                display = if !ent.entity_type.can_inherit() {
                     Display::None
                 } else {
                     Display::Flex
@@ -108,20 +107,26 @@ impl Element for SidebarParent {
             }),
         });
 
-        let name = TextElement::create(TextElementProps {
+        // Name with the type arguments (only the arguments can be edited)
+        let name = TextInput::create(TextInputProps {
             getter: Box::new(|ctx| {
-                if let Some(entity) = sidebar_entity!(ctx => get)
-                    .and_then(|e| e.parent)
-                    .map(|id| ctx.project.connections[id].to.entity)
-                    .and_then(|id| ctx.project.entities.get(id))
-                {
-                    entity.name.clone()
+                if let Some(parent) = sidebar_entity!(ctx => get).and_then(|e| e.parent) {
+                    ctx.project.reference(parent)
                 } else {
                     "".to_string()
                 }
             }),
+            setter: Box::new(|ctx, str| {
+                if let Some(parent) = sidebar_entity!(ctx => get).and_then(|e| e.parent) {
+                    ctx.project.set_arguments(parent, str);
+                }
+            }),
             size: 16.,
             font: fonts::jbmono_medium(),
+            placeholder: None,
+            validator: None,
+            on_blur: None,
+            link: Some(Box::new(follow_reference)),
         });
 
         let remove_button = Button::create(ButtonProps {
@@ -0,0 +1,105 @@
+use crate::{
+    app::{context::EventContext, Tree},
+    data::entity::EntityType,
+    elements::{
+        button::{Button, ButtonProps, ButtonStyle},
+        node::{Element, ElementWithProps},
+        primitives::icon::Symbol,
+        segmented_control::{SegmentedControl, SegmentedControlProps},
+        text_element::{TextElement, TextElementProps},
+    },
+    presentation::fonts,
+};
+use taffy::{
+    prelude::{auto, length, percent},
+    AlignItems, JustifyContent, NodeId, Size, Style,
+};
+
+/// Bulk actions shown instead of the entity details when multiple entities are selected
+pub struct SidebarSelection;
+
+impl Element for SidebarSelection {
+    fn setup(tree: &mut Tree, ctx: &mut EventContext) -> NodeId {
+        let count = TextElement::create(TextElementProps {
+            getter: Box::new(|ctx| format!("{} items selected", ctx.state.sidebar.selection)),
+            size: 16.,
+            font: fonts::jbmono_regular(),
+        });
+
+        let segmented_control = SegmentedControl::create(SegmentedControlProps {
+            items: vec![
+                (Symbol::Class, "Class"),
+                (Symbol::AbstractClass, "Abstract class"),
+                (Symbol::SealedClass, "Sealed class"),
+                (Symbol::Interface, "Interface"),
+                (Symbol::Enum, "Enumeration"),
+            ],
+
+            // Show the type of the first selected entity
+            getter: Box::new(|ctx| {
+                ctx.state
+                    .selection
+                    .first()
+                    .and_then(|&e| ctx.project.entities.get(e))
+                    .map(|e| e.entity_type as usize)
+                    .unwrap_or(0)
+            }),
+
+            setter: Box::new(|ctx, index| {
+                let Ok(ty) = EntityType::try_from(index) else {
+                    return;
+                };
+
+                for &key in ctx.state.selection.iter() {
+                    if ctx.project.entities.contains_key(key) {
+                        ctx.project.set_entity_type(key, ty);
+                    }
+                }
+            }),
+        });
+
+        let delete_button = Button::create(ButtonProps {
+            tooltip: "Delete entities",
+            icon: Symbol::Trash,
+            on_click: Box::new(|ctx| {
+                for entity in std::mem::take(&mut ctx.state.selection) {
+                    if ctx.project.entities.contains_key(entity) {
+                        ctx.project.remove_entity(entity);
+                    }
+                }
+
+                ctx.state.select(None);
+                ctx.state.request_tooltip_update();
+            }),
+            style: ButtonStyle::Segmented,
+        });
+
+        let count = count(tree, ctx);
+        let segmented_control = segmented_control(tree, ctx);
+        let delete_button = delete_button(tree, ctx);
+
+        let right = tree
+            .new_with_children(
+                Style {
+                    gap: length(4.),
+                    ..<_>::default()
+                },
+                &[segmented_control, delete_button],
+            )
+            .unwrap();
+
+        tree.new_with_children(
+            Style {
+                size: Size {
+                    width: percent(1.),
+                    height: auto(),
+                },
+                align_items: Some(AlignItems::Center),
+                justify_content: Some(JustifyContent::SpaceBetween),
+                ..<_>::default()
+            },
+            &[count, right],
+        )
+        .unwrap()
+    }
+}
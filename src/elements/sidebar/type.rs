@@ -38,6 +38,7 @@ impl Element for SidebarType {
                 (Symbol::AbstractClass, "Abstract class"),
                 (Symbol::SealedClass, "Sealed class"),
                 (Symbol::Interface, "Interface"),
+                (Symbol::Enum, "Enumeration"),
             ],
 
             getter: Box::new(|ctx| {
@@ -51,8 +52,8 @@ impl Element for SidebarType {
                     return;
                 };
 
-                if let Some(entity) = sidebar_entity!(ctx => get_mut) {
-                    entity.entity_type = ty;
+                if let Some(entity) = ctx.state.sidebar.entity {
+                    ctx.project.set_entity_type(entity, ty);
                 }
             }),
         });
@@ -64,7 +65,7 @@ impl Element for SidebarType {
             on_click: Box::new(|ctx| {
                 if let Some(entity) = ctx.state.sidebar.entity {
                     ctx.project.remove_entity(entity);
-                    ctx.state.selected_entity = None;
+                    ctx.state.select(None);
                     ctx.state.request_tooltip_update();
                 }
             }),
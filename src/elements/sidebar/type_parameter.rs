@@ -0,0 +1,122 @@
+use super::list::Countable;
+use crate::{
+    app::{context::EventContext, EventTarget, Tree},
+    data::{entity::EntityType, signature},
+    elements::{
+        button::{Button, ButtonProps, ButtonStyle},
+        node::ElementWithProps,
+        primitives::icon::Symbol,
+        sidebar::{follow_reference, sidebar_entity},
+        text_input::{TextInput, TextInputProps},
+        Node,
+    },
+    presentation::fonts,
+};
+use taffy::{
+    prelude::{auto, length, percent},
+    Layout, NodeId, Size, Style,
+};
+
+pub struct SidebarTypeParameter(Layout);
+
+impl Countable for SidebarTypeParameter {
+    fn count(ctx: &EventContext) -> usize {
+        sidebar_entity!(ctx => get)
+            .map(|ent| ent.generics.len())
+            .unwrap_or(0)
+    }
+
+    fn visible(ctx: &EventContext) -> bool {
+        sidebar_entity!(ctx => get).is_some_and(|ent| ent.entity_type != EntityType::Enum)
+    }
+}
+
+impl EventTarget for SidebarTypeParameter {}
+
+impl Node for SidebarTypeParameter {
+    fn layout(&self) -> &Layout {
+        &self.0
+    }
+
+    fn layout_mut(&mut self) -> &mut Layout {
+        &mut self.0
+    }
+}
+
+impl ElementWithProps for SidebarTypeParameter {
+    type Props = usize; // idx
+
+    fn setup(tree: &mut Tree, ctx: &mut EventContext, idx: usize) -> NodeId {
+        tree.add_element(
+            ctx,
+            Style {
+                max_size: Size {
+                    width: percent(1.),
+                    height: auto(),
+                },
+                gap: length(4.),
+                ..<_>::default()
+            },
+            Some(vec![
+                // Name (and the optional constraints)
+                TextInput::create(TextInputProps {
+                    getter: Box::new(move |ctx| {
+                        if let Some(parameter) =
+                            sidebar_entity!(ctx => get).and_then(|e| e.generics.get(idx))
+                        {
+                            parameter.to_string()
+                        } else {
+                            "".to_string()
+                        }
+                    }),
+                    setter: Box::new(move |ctx, str| {
+                        if let Some(parameter) =
+                            sidebar_entity!(ctx => get_mut).and_then(|e| e.generics.get_mut(idx))
+                        {
+                            if let Ok(parsed) = signature::parse_type_parameter(str) {
+                                *parameter = parsed;
+                            }
+                        }
+                    }),
+                    size: 16.,
+                    font: fonts::jbmono_regular(),
+                    placeholder: None,
+                    validator: Some(Box::new(move |ctx, str| {
+                        let parameter = signature::parse_type_parameter(str)?;
+
+                        // The other parameters of the entity can't have the same name
+                        let duplicate = sidebar_entity!(ctx => get).is_some_and(|entity| {
+                            let mut others = entity.generics.iter().enumerate();
+                            others.any(|(i, other)| i != idx && other.name == parameter.name)
+                        });
+
+                        if duplicate {
+                            let start = str.chars().take_while(|c| c.is_whitespace()).count();
+                            return Err(signature::ParseError {
+                                message: format!("Duplicate type parameter `{}`", parameter.name),
+                                span: start..start + parameter.name.chars().count(),
+                            });
+                        }
+
+                        Ok(())
+                    })),
+                    on_blur: None,
+                    link: Some(Box::new(follow_reference)),
+                }),
+                // Delete button
+                Button::create(ButtonProps {
+                    tooltip: "Delete type parameter",
+                    icon: Symbol::Trash,
+                    on_click: Box::new(move |ctx| {
+                        if let Some(entity) = sidebar_entity!(ctx => get_mut) {
+                            entity.generics.remove(idx);
+                            ctx.state.request_tooltip_update();
+                        }
+                    }),
+                    style: ButtonStyle::Segmented,
+                }),
+            ]),
+            |_, _| Self(<_>::default()),
+        )
+    }
+}
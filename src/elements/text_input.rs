@@ -1,6 +1,7 @@
 use super::{
     node::ElementWithProps,
     primitives::{simple_box::SimpleBox, text::Text, traits::Draw},
+    tooltip::{TooltipPosition, TooltipState},
     Node,
 };
 use crate::{
@@ -12,6 +13,7 @@ use crate::{
         context::{EventContext, GetterContext, RenderContext},
         ctx, EventTarget, State, Tree,
     },
+    data::signature::ParseError,
     geometry::{Point, Rect},
     presentation::FontResource,
 };
@@ -21,7 +23,7 @@ use taffy::{
     prelude::{auto, length},
     AvailableSpace, Layout, NodeId, Size, Style,
 };
-use vello::kurbo::{Affine, Line, Stroke};
+use vello::kurbo::{Affine, BezPath, Line, Stroke};
 use winit::{
     event::{KeyEvent, MouseButton},
     keyboard::{Key, NamedKey},
@@ -34,13 +36,28 @@ use std::time::Instant;
 #[cfg(target_arch = "wasm32")]
 use web_time::Instant;
 
+type Getter = Box<dyn Fn(&GetterContext) -> String>;
+type Setter = Box<dyn Fn(&mut EventContext, &str)>;
+type Validator = Box<dyn Fn(&GetterContext, &str) -> Result<(), ParseError>>;
+type Callback = Box<dyn Fn(&mut EventContext)>;
+type Link = Box<dyn Fn(&mut EventContext, &str) -> bool>;
+
 pub struct TextInputProps {
     pub size: f64,
     pub font: &'static FontResource<'static>,
     pub placeholder: Option<String>,
 
-    pub getter: Box<dyn Fn(&GetterContext) -> String>,
-    pub setter: Box<dyn Fn(&mut EventContext, &str)>,
+    pub getter: Getter,
+    pub setter: Setter,
+
+    /// Checked before the setter is called, invalid text is kept (and highlighted) until it's fixed
+    pub validator: Option<Validator>,
+
+    /// Called after the input loses focus (once the mouse is released)
+    pub on_blur: Option<Callback>,
+
+    /// Called with the word under the cursor on main modifier + click, returns whether it was followed
+    pub link: Option<Link>,
 }
 
 #[derive(AnimatedElement)]
@@ -52,6 +69,9 @@ pub struct TextInput {
 
     // Cache the text so the element can be marked as "dirty" when it changes
     text: String,
+    value: String, // The last value returned by the getter
+    error: Option<ParseError>,
+    was_focused: bool,
     measurements: Vec<f32>,
     cursor: usize,
     selection: isize,
@@ -112,6 +132,18 @@ impl TextInput {
         index
     }
 
+    /// Returns the word (identifier) around the character index
+    fn word_at(&self, index: usize) -> String {
+        let chars = self.text.chars().collect::<Vec<_>>();
+        let index = index.min(chars.len());
+        let is_word = |c: &&char| c.is_alphanumeric() || **c == '_';
+
+        let start = chars[..index].iter().rev().take_while(is_word).count();
+        let end = chars[index..].iter().take_while(is_word).count();
+
+        chars[index - start..index + end].iter().collect()
+    }
+
     /// Update the view based on the cursor position
     fn update_view(&mut self) {
         let max = self.layout.size.width;
@@ -172,6 +204,25 @@ impl TextInput {
         });
     }
 
+    /// Validate the text and pass it to the setter if it's valid
+    fn commit(&mut self, ctx: &mut EventContext) {
+        let error = match &self.props.validator {
+            Some(validator) => validator(ctx!(ctx => GetterContext), &self.text).err(),
+            None => None,
+        };
+
+        if error != self.error {
+            self.error = error;
+            ctx.state.request_tooltip_update();
+        }
+
+        if self.error.is_none() {
+            // Consecutive keystrokes are merged into a single undo step
+            ctx.state.history.merge_with(self.node_id);
+            (self.props.setter)(ctx, &self.text);
+        }
+    }
+
     /// Select the entire text
     fn select_all(&mut self) {
         let len = self.text.chars().count();
@@ -333,9 +384,19 @@ impl EventTarget for TextInput {
     fn update(&mut self, ctx: &mut EventContext) {
         let text = (self.props.getter)(ctx!(ctx => GetterContext));
 
-        if text != self.text {
-            self.text = text;
+        // Invalid text stays while it's being edited, unless the value was changed elsewhere
+        let editing = self.error.is_some() && self.focused(ctx.state) && text == self.value;
+
+        if !editing && (text != self.text || self.error.is_some()) {
+            self.text = text.clone();
+            self.error = None;
             self.reset(ctx);
+
+            ctx.state.request_tooltip_update();
+        }
+
+        if text != self.value {
+            self.value = text;
         }
 
         // Loop
@@ -350,6 +411,18 @@ impl EventTarget for TextInput {
         self.animate();
 
         if self.focused(ctx.state) {
+            self.was_focused = true;
+            ctx.state.request_redraw();
+        } else if self.was_focused {
+            // Wait for the click that moved the focus elsewhere to finish
+            if ctx.state.mouse_buttons.is_empty() {
+                self.was_focused = false;
+
+                if let Some(on_blur) = &self.props.on_blur {
+                    on_blur(ctx);
+                }
+            }
+
             ctx.state.request_redraw();
         }
     }
@@ -362,6 +435,7 @@ impl EventTarget for TextInput {
         let rect = Rect::from(self.layout);
         let mut color = ctx.c.colors().text;
         let accent = ctx.c.colors().accent;
+        let red = ctx.c.colors().error;
 
         let offset = if self.focused(ctx.state) {
             self.view
@@ -369,12 +443,13 @@ impl EventTarget for TextInput {
             0
         };
 
-        let text = if self.text.is_empty() && self.props.placeholder.is_some() {
-            color = ctx.c.colors().text_secondary;
+        let text = match &self.props.placeholder {
+            Some(placeholder) if self.text.is_empty() => {
+                color = ctx.c.colors().text_secondary;
 
-            self.props.placeholder.as_ref().unwrap()
-        } else {
-            &self.text.chars().skip(offset).collect::<String>()
+                placeholder
+            }
+            _ => &self.text.chars().skip(offset).collect::<String>(),
         };
 
         Text::new(text, self.layout, font_size, self.props.font, color, true).draw(ctx.c);
@@ -383,6 +458,52 @@ impl EventTarget for TextInput {
             return;
         }
 
+        // Error underline
+        if let Some(error) = &self.error {
+            let start = error.span.start.max(offset);
+            let end = error.span.end.max(start);
+
+            let origin = self
+                .measurements
+                .iter()
+                .take(start)
+                .skip(offset)
+                .sum::<f32>() as f64;
+
+            // Errors at the end of the text don't have any characters to underline
+            let size = match self
+                .measurements
+                .iter()
+                .skip(start)
+                .take(end - start)
+                .sum::<f32>()
+            {
+                0. => font_size * 0.6,
+                size => size as f64,
+            };
+
+            let size = size.min(rect.size.x - origin);
+
+            // Zigzag line below the text
+            let mut path = BezPath::new();
+            let y = font_size * 1.15;
+            let step = font_size * 0.15;
+
+            path.move_to((origin, y));
+            for i in 1..=(size / step).ceil() as usize {
+                let dy = if i % 2 == 1 { -step } else { 0. };
+                path.line_to((origin + (i as f64 * step).min(size), y + dy));
+            }
+
+            ctx.c.scene().stroke(
+                &Stroke::new(1. * scale),
+                Affine::scale(scale).then_translate((rect.origin * scale).into()),
+                red,
+                None,
+                &path,
+            );
+        }
+
         // Selection
         if self.selection != 0 {
             let (start, end) = self.selection_range();
@@ -429,10 +550,24 @@ impl EventTarget for TextInput {
         }
     }
 
-    fn cursor(&self, _: &GetterContext) -> Option<CursorIcon> {
+    fn cursor(&self, ctx: &GetterContext) -> Option<CursorIcon> {
+        if self.props.link.is_some() && ctx.state.main_modifier() {
+            return Some(CursorIcon::Pointer);
+        }
+
         Some(CursorIcon::Text)
     }
 
+    fn tooltip(&self, _: &GetterContext) -> Option<TooltipState> {
+        let error = self.error.as_ref()?;
+
+        Some(TooltipState {
+            text: error.message.clone(),
+            anchor: self.layout.into(),
+            position: TooltipPosition::Bottom,
+        })
+    }
+
     fn on_click(&mut self, ctx: &mut EventContext) -> bool {
         let now = Instant::now();
 
@@ -537,6 +672,16 @@ impl EventTarget for TextInput {
                     return true;
                 }
 
+                if ch.eq_ignore_ascii_case("z") {
+                    if ctx.state.modifiers.shift_key() {
+                        ctx.state.redo();
+                    } else {
+                        ctx.state.undo();
+                    }
+
+                    return true;
+                }
+
                 if ch == "c" || ch == "x" {
                     ctx.state
                         .clipboard
@@ -564,7 +709,7 @@ impl EventTarget for TextInput {
                     self.reset(ctx);
                     self.update_view();
 
-                    (self.props.setter)(ctx, &self.text);
+                    self.commit(ctx);
                     ctx.state.request_redraw();
 
                     return true;
@@ -587,7 +732,7 @@ impl EventTarget for TextInput {
         self.reset(ctx);
         self.update_view();
 
-        (self.props.setter)(ctx, &self.text);
+        self.commit(ctx);
         ctx.state.request_redraw();
 
         true
@@ -598,6 +743,20 @@ impl EventTarget for TextInput {
             return false;
         }
 
+        // Follow the link under the cursor instead of focusing the input
+        if let Some(link) = &self.props.link {
+            if ctx.state.main_modifier() {
+                if !self.focused(ctx.state) {
+                    self.view = 0;
+                }
+
+                let word = self.word_at(self.char_at_cursor(ctx.state.cursor));
+                if !word.is_empty() && link(ctx, &word) {
+                    return true;
+                }
+            }
+        }
+
         ctx.state.capturing = Some(self.node_id);
 
         // Reset selection
@@ -608,6 +767,7 @@ impl EventTarget for TextInput {
             self.view = 0;
             self.cursor = 0;
             ctx.state.focused = Some(self.node_id);
+            ctx.state.history.seal();
         }
 
         // Cursor relative to the text element
@@ -640,6 +800,18 @@ impl EventTarget for TextInput {
         }
     }
 
+    fn on_mouseenter(&mut self, ctx: &mut EventContext) -> bool {
+        ctx.state.request_tooltip_update();
+
+        true
+    }
+
+    fn on_mouseleave(&mut self, ctx: &mut EventContext) -> bool {
+        ctx.state.request_tooltip_update();
+
+        true
+    }
+
     fn on_mouseup(&mut self, ctx: &mut EventContext, button: MouseButton) -> bool {
         if button == MouseButton::Left {
             ctx.state.capturing = None;
@@ -667,10 +839,10 @@ impl Node for TextInput {
         _: &Style,
         _: &mut EventContext,
     ) -> Size<f32> {
-        let mut text = &self.text;
-        if text.is_empty() && self.props.placeholder.is_some() {
-            text = self.props.placeholder.as_ref().unwrap();
-        }
+        let text = match &self.props.placeholder {
+            Some(placeholder) if self.text.is_empty() => placeholder,
+            _ => &self.text,
+        };
 
         let size = Text::measure(text, self.props.size, self.props.font);
         let mut width = size.x as f32;
@@ -713,6 +885,9 @@ impl ElementWithProps for TextInput {
                 node_id,
                 props,
                 text: String::new(),
+                value: String::new(),
+                error: None,
+                was_focused: false,
                 measurements: vec![],
                 cursor: 0,
                 selection: 0,
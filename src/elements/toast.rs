@@ -0,0 +1,271 @@
+use super::{
+    node::Element,
+    primitives::{
+        fancy_box::{BorderOptions, FancyBox, ShadowOptions},
+        simple_box::SimpleBox,
+        text::Text,
+        traits::Draw,
+    },
+    Node,
+};
+use crate::{
+    animations::{
+        animated_property::AnimatedProperty,
+        standard_animation::{Easing, StandardAnimation},
+    },
+    app::{
+        context::{EventContext, GetterContext, RenderContext},
+        error::FileError,
+        EventTarget, Tree,
+    },
+    geometry::{Point, Rect, Size},
+    presentation::fonts,
+};
+use derive_macros::AnimatedElement;
+use std::time::Duration;
+use taffy::{Layout, NodeId};
+use winit::window::CursorIcon;
+
+/// Error message shown at the bottom of the window until it's clicked
+#[derive(AnimatedElement)]
+pub struct Toast {
+    layout: Layout,
+
+    current: Option<FileError>,
+    // The reason split into lines that fit into the toast
+    lines: Vec<String>,
+
+    opacity: AnimatedProperty<StandardAnimation<f32>>,
+}
+
+impl Toast {
+    const TITLE_SIZE: f64 = 14.;
+    const FONT_SIZE: f64 = 12.;
+    const LINE_HEIGHT: f64 = Self::FONT_SIZE * 1.5;
+
+    const MAX_WIDTH: f64 = 420.;
+    const PADDING: f64 = 12.;
+    const MARGIN: f64 = 16.;
+
+    // Space for the accent stripe on the left and the close button on the right
+    const STRIPE: f64 = 4.;
+    const CLOSE: f64 = 24.;
+
+    /// Greedily wraps the words of the text to the given width
+    fn wrap(text: &str, width: f64) -> Vec<String> {
+        let font = fonts::inter_regular();
+        let mut lines: Vec<String> = Vec::new();
+
+        for word in text.split_whitespace() {
+            if let Some(line) = lines.last_mut() {
+                let candidate = format!("{line} {word}");
+
+                if Text::measure(&candidate, Self::FONT_SIZE, font).x <= width {
+                    *line = candidate;
+                    continue;
+                }
+            }
+
+            lines.push(word.to_string());
+        }
+
+        lines
+    }
+
+    fn measure(&self) -> Size {
+        let Some(error) = &self.current else {
+            return Size::default();
+        };
+
+        // The title is ellipsized, which needs some extra space even if it fits
+        let font = fonts::inter_semi_bold();
+        let title = Text::measure(&(error.title() + "…"), Self::TITLE_SIZE, font).x;
+        let lines = self
+            .lines
+            .iter()
+            .map(|line| Text::measure(line, Self::FONT_SIZE, fonts::inter_regular()).x)
+            .fold(title, f64::max);
+
+        Size::new(
+            lines.min(Self::MAX_WIDTH) + Self::PADDING * 2. + Self::STRIPE + Self::CLOSE,
+            Self::TITLE_SIZE * 1.2
+                + self.lines.len() as f64 * Self::LINE_HEIGHT
+                + Self::PADDING * 2.,
+        )
+    }
+}
+
+impl Node for Toast {
+    fn layout(&self) -> &Layout {
+        &self.layout
+    }
+
+    fn layout_mut(&mut self) -> &mut Layout {
+        &mut self.layout
+    }
+}
+
+impl EventTarget for Toast {
+    fn update(&mut self, ctx: &mut EventContext) {
+        if self.animate() {
+            ctx.state.request_redraw();
+        } else if self.current.is_none() {
+            if let Some(error) = &ctx.state.error {
+                self.lines = Self::wrap(&error.reason, Self::MAX_WIDTH);
+                self.current = Some(error.clone());
+                self.opacity.set(1.);
+                ctx.state.request_redraw();
+            }
+        } else if *self.opacity == 0. {
+            self.current = None;
+
+            // Re-run the update to check if there's a new error to show
+            self.update(ctx);
+        } else if self.current != ctx.state.error {
+            self.opacity.set(0.);
+            ctx.state.request_redraw();
+        }
+
+        // Centered at the bottom of the window
+        let size = self.measure();
+        let (width, height) = ctx.c.size();
+        let screen = Size::new(width as f64, height as f64) / ctx.c.scale();
+
+        self.layout.location = taffy::Point {
+            x: ((screen.x - size.x) / 2.) as f32,
+            y: (screen.y - size.y - Self::MARGIN) as f32,
+        };
+        self.layout.size = taffy::Size {
+            width: size.x as f32,
+            height: size.y as f32,
+        };
+    }
+
+    fn render(&self, RenderContext { c, .. }: &mut RenderContext) {
+        let Some(error) = &self.current else {
+            return;
+        };
+
+        let opacity = *self.opacity;
+
+        // Slides up while fading in
+        let rect = Rect::from(self.layout).translate((0., Self::MARGIN * (1. - opacity) as f64));
+
+        FancyBox::new(
+            rect,
+            taffy::Rect::length(1.),
+            8.,
+            c.colors().floating_background.multiply_alpha(opacity),
+            Some(BorderOptions {
+                color: c.colors().border.multiply_alpha(opacity),
+            }),
+            Some(ShadowOptions {
+                color: c.colors().drop_shadow.multiply_alpha(opacity),
+                offset: Point::new(0., 1.),
+                blur_radius: 5.,
+            }),
+        )
+        .draw(c);
+
+        // Accent stripe
+        let stripe = Rect::new(
+            rect.origin + (Self::PADDING / 2., Self::PADDING),
+            (Self::STRIPE, rect.size.y - Self::PADDING * 2.),
+        );
+
+        SimpleBox::new(stripe, 2., c.colors().error.multiply_alpha(opacity)).draw(c);
+
+        let content = Rect::new(
+            rect.origin + (Self::PADDING + Self::STRIPE, Self::PADDING),
+            (
+                rect.size.x - Self::PADDING * 2. - Self::STRIPE - Self::CLOSE,
+                Self::TITLE_SIZE * 1.2,
+            ),
+        );
+
+        Text::new(
+            &error.title(),
+            content,
+            Self::TITLE_SIZE,
+            fonts::inter_semi_bold(),
+            c.colors().text.multiply_alpha(opacity),
+            true,
+        )
+        .draw(c);
+
+        let mut line = Rect::new(
+            content.origin
+                + (
+                    0.,
+                    content.size.y + (Self::LINE_HEIGHT - Self::FONT_SIZE * 1.2),
+                ),
+            (content.size.x, Self::FONT_SIZE * 1.2),
+        );
+
+        for text in self.lines.iter() {
+            Text::new(
+                text,
+                line,
+                Self::FONT_SIZE,
+                fonts::inter_regular(),
+                c.colors().text_secondary.multiply_alpha(opacity),
+                false,
+            )
+            .draw(c);
+
+            line.origin.y += Self::LINE_HEIGHT;
+        }
+
+        // Close button
+        let close = Rect::new(
+            (
+                rect.end().x - Self::PADDING - Self::CLOSE / 2.,
+                content.origin.y,
+            ),
+            (Self::CLOSE / 2., Self::TITLE_SIZE * 1.2),
+        );
+
+        Text::new(
+            "×",
+            close,
+            Self::TITLE_SIZE,
+            fonts::inter_regular(),
+            c.colors().text_secondary.multiply_alpha(opacity),
+            false,
+        )
+        .draw(c);
+    }
+
+    fn cursor(&self, _: &GetterContext) -> Option<CursorIcon> {
+        self.current.as_ref().map(|_| CursorIcon::Pointer)
+    }
+
+    fn on_click(&mut self, ctx: &mut EventContext) -> bool {
+        if self.current.is_none() {
+            return false;
+        }
+
+        ctx.state.error = None;
+        ctx.state.request_cursor_update();
+        ctx.state.request_redraw();
+
+        true
+    }
+}
+
+impl Element for Toast {
+    fn setup(tree: &mut Tree, ctx: &mut EventContext) -> NodeId {
+        tree.add_element(ctx, Default::default(), None, |_, _| Self {
+            layout: Default::default(),
+
+            current: None,
+            lines: Vec::new(),
+
+            opacity: AnimatedProperty::new(StandardAnimation::initialized(
+                0.,
+                Duration::from_millis(200),
+                Easing::EaseInOut,
+            )),
+        })
+    }
+}
@@ -32,6 +32,11 @@ impl EventTarget for Toolbox {
     }
 
     fn on_keydown(&mut self, ctx: &mut EventContext, event: winit::event::KeyEvent) -> bool {
+        // Leave the shortcuts to the other listeners
+        if ctx.state.main_modifier() {
+            return false;
+        }
+
         let pk = event.physical_key;
         let char = event.text.and_then(|t| t.chars().next());
 
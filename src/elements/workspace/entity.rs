@@ -6,10 +6,14 @@ use crate::{
         traits::Interpolate,
     },
     app::{renderer::Canvas, State},
-    data::{entity::EntityType, Entity},
+    data::{
+        entity::{EntityType, Line},
+        Entity,
+    },
     elements::{
         primitives::{
             fancy_box::{BorderOptions, FancyBox, ShadowOptions},
+            simple_box::SimpleBox,
             text::Text,
             traits::Draw,
         },
@@ -20,6 +24,10 @@ use crate::{
 };
 use derive_macros::AnimatedElement;
 use std::time::Duration;
+use vello::kurbo::{Affine, RoundedRect, Stroke};
+
+/// Dash and gap length of the border around the type parameters (in pixels)
+pub const TEMPLATE_DASHES: [f64; 2] = [4., 3.];
 
 #[derive(Debug, AnimatedElement)]
 pub struct EntityItemData {
@@ -79,13 +87,17 @@ impl Item for Entity {
             let has_selection = state.selected_entity.is_some_and(|key| key != self.key);
 
             let is_interface = self.entity_type == EntityType::Interface;
-            let is_sealed = self.entity_type == EntityType::SealedClass;
 
-            // Interfaces can't have children and can't be parents
+            // Interfaces and enums can't have children and can't be parents
             // Sealed classes can't have children but can be parents
-            // Only interfaces can be implemented
-            (parent_tool && (is_interface || (is_sealed && has_selection)))
-                || (impl_tool && has_selection && !is_interface)
+            // Only interfaces can be implemented, and enums can't implement anything
+            if has_selection {
+                (parent_tool && !self.entity_type.can_be_inherited())
+                    || (impl_tool && !is_interface)
+            } else {
+                (parent_tool && !self.entity_type.can_inherit())
+                    || (impl_tool && !self.entity_type.can_implement())
+            }
         };
 
         self.data.opacity.set(if self.data.move_pos.is_some() {
@@ -97,46 +109,11 @@ impl Item for Entity {
         });
 
         // Compute the entity's position and size
-        let mut position: Point = Point::from(self.position) * Workspace::GRID_SIZE;
-        let mut size = Size::ZERO;
-
-        // Name
-        let name = Text::measure(&self.name, 16., title_font(self));
-        size.x = size.x.max(name.x);
-        size.y += name.y;
-
-        if !self.fields.is_empty() {
-            size.y += 8.; // 8px gap
-        }
-
-        // Attributes
-        for field in self.fields.iter() {
-            let attr = Text::measure(&field.to_string(), 12., fonts::jbmono_regular());
-            size.x = size.x.max(attr.x);
-            size.y += attr.y + 8.; // 4px margin
-        }
-
-        if !self.methods.is_empty() {
-            size.y += 8.; // 8px gap between fields and methods
-        }
-
-        for method in self.methods.iter() {
-            let attr = Text::measure(&method.to_string(), 12., fonts::jbmono_regular());
-            size.x = size.x.max(attr.x);
-            size.y += attr.y + 8.; // 4px margin
-        }
-
-        // Padding
-        size += (Workspace::GRID_SIZE, Workspace::GRID_SIZE);
-
-        position -= size / 2.;
-
-        let rect = Rect::new(position, size);
-        self.data.rect.set(rect);
+        self.data.rect.set(bounds(self));
 
         // Animate the selection outline
         self.data.selection_outline.set(
-            if state.selected_entity == Some(self.key) || self.data.move_pos.is_some() {
+            if state.is_selected(self.key) || self.data.move_pos.is_some() {
                 1.
             } else {
                 0.
@@ -147,16 +124,23 @@ impl Item for Entity {
     }
 
     fn render(&self, c: &mut Canvas, _: &State, ws: &Workspace) {
+        self.render_box(c, ws);
+    }
+}
+
+impl Entity {
+    /// Renders the box with the name and members (doesn't depend on the app state)
+    pub fn render_box(&self, c: &mut Canvas, ws: &Workspace) {
         let pos = ws.position();
         let zoom = ws.zoom();
 
         // Offset the position if moving
-        let rect = self
+        let world = self
             .data
             .rect
             .translate(self.data.move_pos.unwrap_or_default());
 
-        let rect = (rect * zoom).translate(-pos);
+        let rect = (world * zoom).translate(-pos);
         let opacity = *self.data.opacity;
 
         // Background
@@ -183,10 +167,27 @@ impl Item for Entity {
 
         let padded: Rect = rect.inset_uniform(16. * zoom);
 
+        // Stereotype
+        let header = if let Some(stereotype) = self.stereotype() {
+            Text::new(
+                stereotype,
+                Rect::new(padded.origin, (padded.size.x, 12. * zoom)),
+                12.0 * zoom,
+                fonts::jbmono_italic(),
+                c.colors().text_secondary.multiply_alpha(opacity),
+                false,
+            )
+            .draw(c);
+
+            (12. * 1.2 + 4.) * zoom // 4px gap
+        } else {
+            0.
+        };
+
         // Name
         Text::new(
             &self.name,
-            Rect::new(padded.origin, (padded.size.x, 16. * zoom)),
+            Rect::new(padded.origin + (0., header), (padded.size.x, 16. * zoom)),
             16.0 * zoom,
             title_font(self),
             c.colors().text.multiply_alpha(opacity),
@@ -194,13 +195,30 @@ impl Item for Entity {
         )
         .draw(c);
 
-        let mut render_property = |str: String, y| {
+        let mut render_property = |member: &Line, y| {
+            let color = c.colors().accent.multiply_alpha(opacity);
+            let origin = padded.origin + (0., y);
+
+            // Abstract methods are in italics and static members are underlined
+            let font = if member.is_abstract {
+                fonts::jbmono_italic()
+            } else {
+                fonts::jbmono_regular()
+            };
+
+            if member.is_static {
+                let width = (Text::measure(&member.text, 12., font).x * zoom).min(padded.size.x);
+                let underline = Rect::new(origin + (0., 14. * zoom), (width, zoom));
+
+                SimpleBox::new(underline, 0., color).draw(c);
+            }
+
             Text::new(
-                &str,
-                Rect::new(padded.origin + (0., y), (padded.size.x, 12. * zoom)),
+                &member.text,
+                Rect::new(origin, (padded.size.x, 12. * zoom)),
                 12.0 * zoom,
-                fonts::jbmono_regular(),
-                c.colors().accent.multiply_alpha(opacity),
+                font,
+                color,
                 false,
             )
             .draw(c);
@@ -208,27 +226,129 @@ impl Item for Entity {
 
         // Attributes
         let line = (12. * 1.2 + 8.) * zoom; // 4x margin
-        let mut y = (16. * 1.2 + 8.) * zoom; // 8px gap
-        for field in self.fields.iter() {
-            render_property(field.to_string(), y);
+        let mut y = header + 16. * 1.2 * zoom;
+        for section in self.sections().iter().filter(|s| !s.is_empty()) {
+            y += 8. * zoom; // 8px gap
+
+            for property in section {
+                render_property(property, y);
 
-            y += line;
+                y += line;
+            }
         }
 
-        if !self.fields.is_empty() {
-            y += 8. * zoom; // 8px gap
+        // Type parameters
+        if let Some(template) = template(self, world) {
+            let template = (template * zoom).translate(-pos);
+            let scale = c.scale() * zoom;
+
+            SimpleBox::new(
+                template,
+                4. * zoom,
+                c.colors().floating_background.multiply_alpha(opacity),
+            )
+            .draw(c);
+
+            let border = c.colors().border.multiply_alpha(opacity);
+            c.scene().stroke(
+                &Stroke::new(2.).with_dashes(0., TEMPLATE_DASHES),
+                Affine::scale(scale),
+                border,
+                None,
+                &RoundedRect::from_rect((template / zoom).into(), 4.),
+            );
+
+            for (i, parameter) in self.generics.iter().enumerate() {
+                let y = (4. + i as f64 * 12. * 1.2) * zoom;
+
+                Text::new(
+                    &parameter.to_string(),
+                    Rect::new(
+                        template.origin + (6. * zoom, y),
+                        (template.size.x, 12. * zoom),
+                    ),
+                    12.0 * zoom,
+                    fonts::jbmono_regular(),
+                    c.colors().text_secondary.multiply_alpha(opacity),
+                    false,
+                )
+                .draw(c);
+            }
         }
+    }
+}
 
-        for method in self.methods.iter() {
-            render_property(method.to_string(), y);
+/// Size of the box with the type parameters, one per line (in pixels, without zoom)
+fn template_size(entity: &Entity) -> Option<Size> {
+    if entity.generics.is_empty() {
+        return None;
+    }
 
-            y += line;
+    let mut size = Size::ZERO;
+
+    for parameter in &entity.generics {
+        let text = Text::measure(&parameter.to_string(), 12., fonts::jbmono_regular());
+        size.x = size.x.max(text.x);
+        size.y += text.y;
+    }
+
+    Some(size + (12., 8.)) // 6px and 4px padding
+}
+
+/// The box with the type parameters of a generic entity, which overlaps its top right corner (like in UML)
+pub fn template(entity: &Entity, rect: Rect) -> Option<Rect> {
+    let size = template_size(entity)?;
+    let origin = rect.origin + (rect.size.x + 8. - size.x, 12. - size.y);
+
+    Some(Rect::new(origin, size))
+}
+
+/// Computes the size of the entity in the workspace (in pixels, without zoom)
+pub fn measure(entity: &Entity) -> Size {
+    let mut size = Size::ZERO;
+
+    // Stereotype
+    if let Some(stereotype) = entity.stereotype() {
+        let stereotype = Text::measure(stereotype, 12., fonts::jbmono_italic());
+        size.x = size.x.max(stereotype.x);
+        size.y += stereotype.y + 4.; // 4px gap
+    }
+
+    // Name
+    let name = Text::measure(&entity.name, 16., title_font(entity));
+    size.x = size.x.max(name.x);
+    size.y += name.y;
+
+    // Attributes
+    for section in entity.sections().iter().filter(|s| !s.is_empty()) {
+        size.y += 8.; // 8px gap between the sections
+
+        for property in section {
+            let attr = Text::measure(&property.text, 12., fonts::jbmono_regular());
+            size.x = size.x.max(attr.x);
+            size.y += attr.y + 8.; // 4px margin
         }
     }
+
+    // The type parameters shouldn't stick out on the left
+    if let Some(template) = template_size(entity) {
+        size.x = size.x.max(template.x);
+    }
+
+    // Padding
+    size + (Workspace::GRID_SIZE, Workspace::GRID_SIZE)
+}
+
+/// Computes the rect of the entity in the workspace (in pixels, without zoom)
+pub fn bounds(entity: &Entity) -> Rect {
+    let size = measure(entity);
+    let position = Point::from(entity.position) * Workspace::GRID_SIZE - size / 2.;
+
+    Rect::new(position, size)
 }
 
 #[inline]
-fn title_font(ent: &Entity) -> &FontResource {
+fn title_font(ent: &Entity) -> &FontResource<'_> {
     match ent.entity_type {
         EntityType::AbstractClass => fonts::jbmono_bold_italic(),
         _ => fonts::jbmono_bold(),
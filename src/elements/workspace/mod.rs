@@ -1,6 +1,7 @@
 pub mod connection;
 pub mod entity;
 pub mod item;
+#[allow(clippy::module_inception)]
 pub mod workspace;
 
 pub use workspace::*;
@@ -1,20 +1,32 @@
+use super::entity::bounds;
 use super::item::Item;
 use crate::{
     animations::{animated_property::AnimatedProperty, delta_animation::DeltaAnimation},
     app::{
         context::{EventContext, GetterContext, RenderContext},
         event_target::WheelEvent,
+        renderer::Canvas,
         EventTarget, State, Tree,
     },
     data::{
         entity::EntityType,
         project::{ConnectionKey, EntityKey},
-        Entity, Project,
+        Entity, Fragment, Project,
     },
-    elements::{node::Element, toolbox_item::Tool, Node},
-    geometry::{Point, Rect, Vec2},
+    elements::{
+        node::Element,
+        primitives::{
+            fancy_box::{BorderOptions, FancyBox},
+            traits::Draw,
+        },
+        toolbox_item::Tool,
+        Node,
+    },
+    geometry::{Point, Rect, Size, Vec2},
 };
+use clipboard::ClipboardProvider;
 use derive_macros::AnimatedElement;
+use serde::{Deserialize, Serialize};
 use taffy::{Layout, NodeId, Position, Style};
 use vello::{
     kurbo::{Affine, Circle},
@@ -26,6 +38,34 @@ use winit::{
     window::CursorIcon,
 };
 
+/// Position and zoom of the workspace
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WorkspaceView {
+    pub position: Vec2,
+    pub zoom: f64,
+}
+
+impl Default for WorkspaceView {
+    fn default() -> Self {
+        Self {
+            position: Vec2::default(),
+            zoom: 1.,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct WorkspaceState {
+    /// The current view, kept up to date by the workspace
+    pub view: WorkspaceView,
+
+    /// The view to jump to on the next update (e.g. after restoring a session)
+    pub restore: Option<WorkspaceView>,
+
+    /// The entity to move to the center of the view on the next update
+    pub focus: Option<EntityKey>,
+}
+
 #[derive(AnimatedElement)]
 pub struct Workspace {
     layout: Layout,
@@ -42,6 +82,12 @@ pub struct Workspace {
     ///
     /// Used for moving entities
     move_start_point: Option<Point>,
+
+    /// The start and end point of the selection rectangle
+    marquee: Option<(Point, Point)>,
+
+    /// Entities that were already selected when the selection rectangle was started (shift-drag)
+    marquee_base: Vec<EntityKey>,
 }
 
 impl Workspace {
@@ -109,14 +155,126 @@ impl Workspace {
             .find(|&key| (*project.entities[*key].data.rect * *self.zoom).contains(point))
             .copied()
     }
+
+    /// Creates a workspace that isn't a part of the tree, used for rendering the items elsewhere (e.g. exporting an image)
+    pub fn detached(position: Vec2, zoom: f64) -> Self {
+        Self {
+            layout: Default::default(),
+            node_id: NodeId::from(u64::MAX),
+
+            position: AnimatedProperty::new(DeltaAnimation::initialized(position, 30.)),
+            zoom: AnimatedProperty::new(DeltaAnimation::initialized(zoom, 30.)),
+
+            previous_tool: None,
+            hovered_entity: None,
+            hovered_connection: None,
+            move_start_point: None,
+
+            marquee: None,
+            marquee_base: Vec::new(),
+        }
+    }
+
+    /// Computes the rects and paths of all the items right away, without animating them
+    ///
+    /// Used when there's no window to run the updates (e.g. rendering from the command line)
+    pub fn layout(project: &mut Project) {
+        for (key, entity) in project.entities.iter_mut() {
+            entity.data.rect.reset(bounds(entity));
+            let rect = entity.get_rect();
+
+            for &conn in entity.connections.iter() {
+                if let Some(connection) = project.connections.get_mut(conn) {
+                    connection.update_origin(key, rect, true);
+                }
+            }
+        }
+    }
+
+    /// Renders the connections and entities without the background, the selection and the editing points
+    pub fn render_diagram(&self, c: &mut Canvas, project: &Project) {
+        // Connections
+        for (_, conn) in project.connections.iter() {
+            conn.render_path(c, self)
+        }
+
+        // Entities
+        for entity in project.ordered_entities.iter() {
+            project.entities[*entity].render_box(c, self);
+        }
+    }
+
+    /// Snaps the moved entities to the grid, along with the explicit points of the connections between them
+    fn drop_entities(&mut self, ctx: &mut EventContext) {
+        let mut moved = Vec::new();
+        let mut offset = None;
+
+        for (key, entity) in ctx.project.entities.iter_mut() {
+            let Some(pos) = entity.data.move_pos.take() else {
+                continue;
+            };
+
+            // Set the rect origin for a smooth transition
+            let rect = entity.data.rect.translate(pos);
+            entity.data.rect.reset(rect);
+
+            let position = (rect.center() / Workspace::GRID_SIZE).into();
+            offset.get_or_insert(Point::from(position) - Point::from(entity.position));
+
+            entity.position = position;
+            entity.update(ctx.state, self);
+
+            moved.push(key);
+        }
+
+        for &key in moved.iter() {
+            let rect = ctx.project.entities[key].get_rect();
+            let rect = Rect::new(rect.center().round() - rect.size / 2., rect.size);
+
+            for conn in ctx.project.get_entity_connections(key) {
+                let connection = &mut ctx.project.connections[conn];
+                connection.update_origin(key, rect, false);
+
+                // Move the points only once, when handling the "from" entity
+                if let Some(offset) = offset {
+                    if connection.from.entity == key && moved.contains(&connection.to.entity) {
+                        connection.translate_points(offset);
+                    }
+                }
+            }
+        }
+
+        ctx.state.request_redraw();
+    }
 }
 
 impl EventTarget for Workspace {
     fn update(&mut self, ctx: &mut EventContext) {
+        if let Some(view) = ctx.state.workspace.restore.take() {
+            self.position.reset(view.position);
+            self.zoom.reset(view.zoom);
+            ctx.state.request_redraw();
+        }
+
+        if let Some(entity) = ctx.state.workspace.focus.take() {
+            if let Some(entity) = ctx.project.entities.get(entity) {
+                let center = entity.get_rect().center() * Self::GRID_SIZE;
+                let size = Size::from(self.layout.size);
+
+                self.position.set(center * *self.zoom - size / 2.);
+                ctx.state.request_redraw();
+            }
+        }
+
         if self.animate() {
             ctx.state.request_redraw();
         }
 
+        ctx.state.workspace.view = WorkspaceView {
+            position: *self.position.get_target(),
+            zoom: *self.zoom.get_target(),
+        };
+
         // Entities
         let mut redraw = false;
         for (key, entity) in ctx.project.entities.iter_mut() {
@@ -186,6 +344,23 @@ impl EventTarget for Workspace {
         for entity in project.ordered_entities.iter() {
             project.entities[*entity].render(c, state, self);
         }
+
+        // Selection rectangle
+        if let Some((start, end)) = self.marquee {
+            let rect = (Rect::from_points(start, end) * *self.zoom).translate(-*self.position);
+
+            FancyBox::new(
+                rect,
+                taffy::Rect::length(1.),
+                2.,
+                colors.accent.multiply_alpha(0.1),
+                Some(BorderOptions {
+                    color: colors.accent,
+                }),
+                None,
+            )
+            .draw(c);
+        }
     }
 
     fn cursor(&self, ctx: &GetterContext) -> Option<CursorIcon> {
@@ -197,23 +372,75 @@ impl EventTarget for Workspace {
             None
         }
     }
-    
+
     fn on_keydown(&mut self, ctx: &mut EventContext, event: KeyEvent) -> bool {
         let key = event.logical_key;
         if matches!(key, Key::Named(NamedKey::Space)) {
             self.select_hand(ctx.state);
             return true;
         }
-        
-        if matches!(key, Key::Named(NamedKey::Delete)) && ctx.state.tool == Tool::Select {
-            if let Some(entity) = ctx.state.selected_entity {
-                ctx.project.remove_entity(entity);
-                ctx.state.selected_entity = None;
+
+        if let Key::Character(ch) = &key {
+            if ctx.state.main_modifier() && ch.eq_ignore_ascii_case("z") {
+                if ctx.state.modifiers.shift_key() {
+                    ctx.state.redo();
+                } else {
+                    ctx.state.undo();
+                }
+
+                return true;
+            }
+
+            // Copy / cut the selected entities
+            if ctx.state.main_modifier() && (ch == "c" || ch == "x") {
+                let selection = ctx.state.selection.iter().copied().collect::<Vec<_>>();
+                let Some(json) = Fragment::copy(ctx.project, &selection) else {
+                    return false;
+                };
+
+                _ = ctx.state.clipboard.set_contents(json);
+
+                if ch == "x" {
+                    for entity in selection {
+                        ctx.project.remove_entity(entity);
+                    }
+
+                    ctx.state.select(None);
+                    ctx.state.request_redraw();
+                }
+
+                return true;
+            }
+
+            // Paste entities and select them
+            if ctx.state.main_modifier() && ch == "v" {
+                let json = ctx.state.clipboard.get_contents().unwrap_or_default();
+                let Some(entities) = Fragment::paste(ctx.project, &json) else {
+                    return false;
+                };
+
+                ctx.state.tool = Tool::Select;
+                ctx.state.set_selection(entities);
+                ctx.state.request_cursor_update();
                 ctx.state.request_redraw();
+
                 return true;
             }
         }
 
+        if matches!(key, Key::Named(NamedKey::Delete))
+            && ctx.state.tool == Tool::Select
+            && !ctx.state.selection.is_empty()
+        {
+            for entity in std::mem::take(&mut ctx.state.selection) {
+                ctx.project.remove_entity(entity);
+            }
+
+            ctx.state.select(None);
+            ctx.state.request_redraw();
+            return true;
+        }
+
         false
     }
 
@@ -260,7 +487,7 @@ impl EventTarget for Workspace {
                     Tool::Implementation => ctx.project.implement(from, to),
                     _ => unreachable!(),
                 } {
-                    ctx.state.selected_entity = Some(to);
+                    ctx.state.select(Some(to));
                     ctx.state.request_redraw();
                     return true;
                 }
@@ -310,15 +537,35 @@ impl EventTarget for Workspace {
             }
         }
 
-        // Move entity
-        ctx.project.entity_mut(self.hovered_entity, |entity| {
-            if left && ctx.state.tool == Tool::Select {
-                self.move_start_point = Some(point);
-                entity.data.move_pos = Some(Vec2::ZERO);
-            }
+        if left && ctx.state.tool == Tool::Select {
+            if let Some(entity) = self.hovered_entity {
+                // Shift toggles the entity, otherwise it replaces the selection (unless it's already a part of it)
+                if ctx.state.modifiers.shift_key() {
+                    ctx.state.toggle_selection(entity);
+                } else if !ctx.state.is_selected(entity) {
+                    ctx.state.select(Some(entity));
+                }
+
+                // Move the whole selection
+                if ctx.state.is_selected(entity) {
+                    self.move_start_point = Some(point);
 
-            false
-        });
+                    for &key in ctx.state.selection.iter() {
+                        ctx.project.entities[key].data.move_pos = Some(Vec2::ZERO);
+                    }
+                }
+
+                ctx.state.request_redraw();
+            } else {
+                // Start the selection rectangle
+                self.marquee = Some((point, point));
+                self.marquee_base = if ctx.state.modifiers.shift_key() {
+                    ctx.state.selection.iter().copied().collect()
+                } else {
+                    Vec::new()
+                };
+            }
+        }
 
         // Create new entity
         if left && ctx.state.tool == Tool::Entity {
@@ -329,7 +576,7 @@ impl EventTarget for Workspace {
             );
             let key = ctx.project.add_entity(entity);
 
-            ctx.state.selected_entity = Some(key);
+            ctx.state.select(Some(key));
             ctx.state.request_redraw();
         }
 
@@ -355,22 +602,42 @@ impl EventTarget for Workspace {
             let cursor = self.cursor_to_point(ctx.state.cursor);
             let diff = cursor - old;
 
-            // Move the selected entity
-            if ctx.project.entity_mut(self.hovered_entity, |entity| {
-                entity.data.move_pos = Some(diff);
-                ctx.state.request_redraw();
+            // Move the selected entities
+            for &key in ctx.state.selection.iter() {
+                let Some(entity) = ctx.project.entities.get_mut(key) else {
+                    continue;
+                };
 
-                true
-            }) {
-                let key = self.hovered_entity.unwrap();
-                let rect = ctx.project.entities[key].get_rect();
+                entity.data.move_pos = Some(diff);
+                let rect = entity.get_rect();
 
                 for conn in ctx.project.get_entity_connections(key) {
                     ctx.project.connections[conn].update_origin(key, rect, true);
                 }
-
-                return true;
             }
+
+            ctx.state.request_redraw();
+            return true;
+        }
+
+        // Select the entities inside of the selection rectangle
+        if let Some((start, _)) = self.marquee {
+            let end = self.cursor_to_point(cursor);
+            self.marquee = Some((start, end));
+
+            let rect = Rect::from_points(start, end);
+            let inside = ctx
+                .project
+                .ordered_entities
+                .iter()
+                .copied()
+                .filter(|&key| ctx.project.entities[key].data.rect.intersects(&rect));
+
+            ctx.state
+                .set_selection(self.marquee_base.iter().copied().chain(inside));
+            ctx.state.request_redraw();
+
+            return true;
         }
 
         // Hovered entity
@@ -428,7 +695,7 @@ impl EventTarget for Workspace {
     }
 
     fn on_mouseup(&mut self, ctx: &mut EventContext, _: MouseButton) -> bool {
-        self.move_start_point = None;
+        let moved = self.move_start_point.take().is_some();
 
         let left = ctx.state.mouse_buttons.contains(&MouseButton::Left);
         let middle = ctx.state.mouse_buttons.contains(&MouseButton::Middle);
@@ -457,41 +724,46 @@ impl EventTarget for Workspace {
             return true;
         }
 
-        if ctx.project.entity_mut(self.hovered_entity, |entity| {
-            // Snap the entity to the grid
-            let pos = entity.data.move_pos.take();
-            if let Some(pos) = pos {
-                // Set the rect origin for a smooth transition
-                let rect = entity.data.rect.translate(pos);
-                entity.data.rect.reset(rect);
+        // Finish the selection rectangle
+        if let Some((start, end)) = self.marquee.take() {
+            self.marquee_base.clear();
 
-                entity.position = (rect.center() / Workspace::GRID_SIZE).into();
-                if entity.update(ctx.state, self) {
-                    ctx.state.request_redraw();
-                }
-            }
-
-            if ctx.state.tool == Tool::Select || ctx.state.selected_entity.is_none() {
-                ctx.state.selected_entity = Some(entity.key);
+            // A plain click (without dragging) clears the selection below
+            if start != end || ctx.state.modifiers.shift_key() {
+                ctx.state.request_redraw();
+                return true;
             }
+        }
 
-            ctx.state.request_redraw();
+        let dragged = self
+            .hovered_entity
+            .and_then(|key| ctx.project.entities.get(key))
+            .and_then(|entity| entity.data.move_pos)
+            .is_some_and(|pos| pos != Vec2::ZERO);
 
-            true
-        }) {
-            let key = self.hovered_entity.unwrap();
-            let rect = ctx.project.entities[key].get_rect();
-            let rect = Rect::new(rect.center().round() - rect.size / 2., rect.size);
+        if moved {
+            self.drop_entities(ctx);
+        }
 
-            for conn in ctx.project.get_entity_connections(key) {
-                let connection = &mut ctx.project.connections[conn];
-                connection.update_origin(key, rect, false);
+        if let Some(key) = self
+            .hovered_entity
+            .filter(|&key| ctx.project.entities.contains_key(key))
+        {
+            if ctx.state.tool == Tool::Select {
+                // Clicking an entity inside of a group selects only that entity
+                if moved && !dragged && !ctx.state.modifiers.shift_key() {
+                    ctx.state.select(Some(key));
+                }
+            } else if ctx.state.selected_entity.is_none() {
+                ctx.state.select(Some(key));
             }
 
+            ctx.state.request_redraw();
+
             return true;
         }
 
-        ctx.state.selected_entity = None;
+        ctx.state.select(None);
         ctx.state.request_redraw();
 
         true
@@ -557,6 +829,9 @@ impl Element for Workspace {
                 hovered_entity: None,
                 hovered_connection: None,
                 move_start_point: None,
+
+                marquee: None,
+                marquee_base: Vec::new(),
             }
         })
     }
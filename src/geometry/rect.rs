@@ -43,7 +43,7 @@ impl Rect {
         let inset = Vec2::new(inset, inset);
         self.inset(Rect::new(inset, inset))
     }
-    
+
     // Inset the rect using precalculated directional insets - needed for taffy::Rect
     pub fn inset_directional(self, insets: impl Into<Rect>) -> Self {
         let insets = insets.into();
@@ -60,6 +60,23 @@ impl Rect {
         }
     }
 
+    /// Creates a rect spanning two arbitrary corners
+    pub fn from_points(a: impl Into<Point>, b: impl Into<Point>) -> Self {
+        let (a, b) = (a.into(), b.into());
+        let origin = Point::new(a.x.min(b.x), a.y.min(b.y));
+        let end = Point::new(a.x.max(b.x), a.y.max(b.y));
+
+        Self::new(origin, end - origin)
+    }
+
+    pub fn intersects(&self, other: &Rect) -> bool {
+        let (end, other_end) = (self.end(), other.end());
+        self.origin.x <= other_end.x
+            && other.origin.x <= end.x
+            && self.origin.y <= other_end.y
+            && other.origin.y <= end.y
+    }
+
     pub fn contains(&self, point: impl Into<Point>) -> bool {
         let point = point.into();
         point.x >= self.origin.x
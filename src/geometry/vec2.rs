@@ -133,11 +133,11 @@ impl<V: Into<Vec2>> Sub<V> for Vec2 {
     }
 }
 
-impl Into<taffy::Size<taffy::Dimension>> for Vec2 {
-    fn into(self) -> taffy::Size<taffy::Dimension> {
+impl From<Vec2> for taffy::Size<taffy::Dimension> {
+    fn from(val: Vec2) -> Self {
         taffy::Size {
-            width: length(self.x as f32),
-            height: length(self.y as f32),
+            width: length(val.x as f32),
+            height: length(val.y as f32),
         }
     }
 }
@@ -2,6 +2,8 @@
 
 mod animations;
 mod app;
+#[cfg(not(target_arch = "wasm32"))]
+mod cli;
 mod data;
 mod elements;
 mod geometry;
@@ -16,6 +18,12 @@ use winit::event_loop::EventLoop;
 #[cfg(not(target_arch = "wasm32"))]
 #[pollster::main]
 async fn main() {
+    // Commands run headless, the window is only opened without one
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if let Some(code) = cli::run(&args).await {
+        std::process::exit(code);
+    }
+
     run().await.unwrap();
 }
 
@@ -7,6 +7,8 @@ pub struct Colors {
     pub floating_background: Color,
     pub border: Color,
     pub accent: Color,
+    pub error: Color,
+    pub warning: Color,
     pub icon_active: Color,
     pub icon_inactive: Color,
 
@@ -25,6 +27,8 @@ impl Colors {
         floating_background: Color::from_rgb8(255, 255, 255),
         border: Color::from_rgb8(230, 230, 230),
         accent: Color::from_rgb8(13, 153, 255),
+        error: Color::from_rgb8(220, 38, 38),
+        warning: Color::from_rgb8(217, 119, 6),
         icon_active: Color::WHITE,
         icon_inactive: Color::BLACK,
 
@@ -42,6 +46,8 @@ impl Colors {
         floating_background: Color::from_rgb8(44, 44, 44),
         border: Color::from_rgb8(68, 68, 68),
         accent: Color::from_rgb8(12, 140, 233),
+        error: Color::from_rgb8(248, 113, 113),
+        warning: Color::from_rgb8(251, 191, 36),
         icon_active: Color::WHITE,
         icon_inactive: Color::WHITE,
 
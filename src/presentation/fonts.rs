@@ -88,7 +88,7 @@ impl FontResource<'_> {
         }
     }
 
-    pub fn metrics(&self, font_size: f32) -> GlyphMetrics {
+    pub fn metrics(&self, font_size: f32) -> GlyphMetrics<'_> {
         self.table
             .glyph_metrics(Size::new(font_size), &self.location)
     }
@@ -3,6 +3,7 @@ mod main_modifier;
 mod resize;
 mod screenshot;
 mod scroll;
+mod storage;
 mod upload;
 
 pub use download::*;
@@ -10,4 +11,5 @@ pub use main_modifier::*;
 pub use resize::*;
 pub use screenshot::*;
 pub use scroll::*;
+pub use storage::*;
 pub use upload::*;
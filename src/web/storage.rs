@@ -0,0 +1,26 @@
+use web_sys::Storage;
+
+fn local_storage() -> Option<Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+pub fn get_item(key: &str) -> Option<String> {
+    local_storage()?.get_item(key).ok()?
+}
+
+/// Returns false if the value couldn't be stored (e.g. the storage is full)
+pub fn set_item(key: &str, value: &str) -> bool {
+    local_storage().is_some_and(|storage| storage.set_item(key, value).is_ok())
+}
+
+pub fn remove_item(key: &str) {
+    if let Some(storage) = local_storage() {
+        _ = storage.remove_item(key);
+    }
+}
+
+pub fn confirm(message: &str) -> bool {
+    web_sys::window()
+        .and_then(|window| window.confirm_with_message(message).ok())
+        .unwrap_or_default()
+}
@@ -2,7 +2,7 @@ use crate::app::AppUserEvent;
 use web_sys::{
     js_sys::Uint8Array,
     wasm_bindgen::{prelude::Closure, JsCast, JsValue},
-    Event, HtmlInputElement,
+    DragEvent, Event, HtmlInputElement,
 };
 use winit::event_loop::EventLoopProxy;
 
@@ -14,7 +14,6 @@ pub fn setup_file_picker(ev_proxy: EventLoopProxy<AppUserEvent>) {
     let input = input.dyn_into::<HtmlInputElement>().unwrap();
 
     input.set_type("file");
-    input.set_accept(".bin,application/octet-stream,.json,application/json");
 
     document.body().unwrap().append_child(&input).unwrap();
 
@@ -57,11 +56,60 @@ pub fn setup_file_picker(ev_proxy: EventLoopProxy<AppUserEvent>) {
     on_change.forget();
 }
 
-pub fn open_file_picker() {
+pub fn open_file_picker(accept: &str) {
     let document = web_sys::window().unwrap().document().unwrap();
 
     let input = document.get_element_by_id("file-input").unwrap();
     let input = input.dyn_into::<HtmlInputElement>().unwrap();
 
+    input.set_accept(accept);
     input.click();
 }
+
+/// Loads the files dropped onto the page (instead of the browser opening them)
+pub fn setup_drop_target(ev_proxy: EventLoopProxy<AppUserEvent>) {
+    let window = web_sys::window().unwrap();
+
+    // The drop event is only fired if the default action of dragover is prevented
+    let on_drag_over = Closure::wrap(Box::new(move |event: DragEvent| {
+        event.prevent_default();
+    }) as Box<dyn FnMut(_)>);
+
+    let on_drop = Closure::wrap(Box::new(move |event: DragEvent| {
+        event.prevent_default();
+
+        let Some(file) = event
+            .data_transfer()
+            .and_then(|data| data.files())
+            .and_then(|files| files.get(0))
+        else {
+            return;
+        };
+
+        let ev_proxy = ev_proxy.clone();
+        let name = file.name();
+
+        let parse = Closure::once(move |array_buffer: JsValue| {
+            let vec = Uint8Array::new(&array_buffer).to_vec();
+
+            ev_proxy
+                .send_event(AppUserEvent::FileLoaded(vec, name))
+                .unwrap();
+        });
+
+        _ = file.array_buffer().then(&parse);
+
+        // The closure has to outlive the promise
+        parse.forget();
+    }) as Box<dyn FnMut(_)>);
+
+    window
+        .add_event_listener_with_callback("dragover", on_drag_over.as_ref().unchecked_ref())
+        .unwrap();
+    window
+        .add_event_listener_with_callback("drop", on_drop.as_ref().unchecked_ref())
+        .unwrap();
+
+    on_drag_over.forget();
+    on_drop.forget();
+}